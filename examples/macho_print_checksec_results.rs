@@ -17,18 +17,23 @@ fn main() {
                             Mach::Binary(macho) => {
                                 println!(
                                     "{:#?}",
-                                    CheckSecResults::parse(&macho)
+                                    CheckSecResults::parse(&macho, &buf)
                                 );
                             }
                             Mach::Fat(fatmach) => {
-                                for (idx, _) in
+                                for (idx, arch) in
                                     fatmach.iter_arches().enumerate()
                                 {
+                                    let arch = arch.unwrap();
                                     let container: MachO =
                                         fatmach.get(idx).unwrap();
+                                    let slice = &buf[arch.offset as usize..]
+                                        [..arch.size as usize];
                                     println!(
                                         "{:#?}",
-                                        CheckSecResults::parse(&container)
+                                        CheckSecResults::parse(
+                                            &container, slice
+                                        )
                                     );
                                 }
                             }
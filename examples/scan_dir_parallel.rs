@@ -0,0 +1,26 @@
+extern crate checksec;
+extern crate rayon;
+
+use checksec::scan::{files, scan_file, ScanOptions};
+use rayon::prelude::*;
+use std::env;
+use std::path::Path;
+
+fn main() {
+    let argv: Vec<String> = env::args().collect();
+    if argv.len() == 2 {
+        let options = ScanOptions::default();
+        files(Path::new(&argv[1]), &options)
+            .par_bridge()
+            .flat_map_iter(|file| match file {
+                Ok(file) => scan_file(&file, &options),
+                Err(err) => vec![Err(err)],
+            })
+            .for_each(|result| match result {
+                Ok(binary) => println!("{}", binary),
+                Err(err) => eprintln!("{}", err),
+            });
+    } else {
+        eprintln!("Usage: scan_dir_parallel <directory>");
+    }
+}
@@ -1,16 +1,34 @@
 #[cfg(feature = "color")]
 use colored::Colorize;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use goblin::error::Error;
+#[cfg(feature = "macho")]
+use goblin::mach::{
+    constants::cputype::get_arch_name_from_types, Mach, MachO,
+};
+use goblin::Object;
+use memmap::Mmap;
+use sha2::{Digest as _, Sha256};
+use std::fmt::Write as _;
+use std::io::Read;
+use std::path::Path;
+use std::time::SystemTime;
+use std::{fs, io};
+
 #[cfg(feature = "elf")]
-use checksec::elf;
+use crate::elf;
+use crate::entropy;
 #[cfg(feature = "macho")]
-use checksec::macho;
+use crate::macho;
 #[cfg(feature = "pe")]
-use checksec::pe;
+use crate::pe;
+#[cfg(feature = "wasm")]
+use crate::wasm;
 
-#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Debug, Deserialize, PartialEq, JsonSchema, Serialize)]
 pub enum BinType {
     #[cfg(feature = "elf")]
     Elf32,
@@ -24,6 +42,8 @@ pub enum BinType {
     MachO32,
     #[cfg(feature = "macho")]
     MachO64,
+    #[cfg(feature = "wasm")]
+    Wasm,
 }
 #[cfg(not(feature = "color"))]
 impl fmt::Display for BinType {
@@ -41,6 +61,8 @@ impl fmt::Display for BinType {
             Self::MachO32 => write!(f, "MachO32"),
             #[cfg(feature = "macho")]
             Self::MachO64 => write!(f, "MachO64"),
+            #[cfg(feature = "wasm")]
+            Self::Wasm => write!(f, "Wasm"),
         }
     }
 }
@@ -60,11 +82,135 @@ impl fmt::Display for BinType {
             Self::MachO32 => write!(f, "{}", "MachO32".bold().underline()),
             #[cfg(feature = "macho")]
             Self::MachO64 => write!(f, "{}", "MachO64".bold().underline()),
+            #[cfg(feature = "wasm")]
+            Self::Wasm => write!(f, "{}", "Wasm".bold().underline()),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Cross-format state of a single security mitigation
+#[derive(
+    Clone, Copy, Debug, Deserialize, PartialEq, JsonSchema, Serialize,
+)]
+pub enum Mitigation {
+    Enabled,
+    Partial,
+    Disabled,
+    NotApplicable,
+}
+impl From<bool> for Mitigation {
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            Self::Enabled
+        } else {
+            Self::Disabled
+        }
+    }
+}
+impl From<Option<bool>> for Mitigation {
+    fn from(enabled: Option<bool>) -> Self {
+        enabled.map_or(Self::NotApplicable, Self::from)
+    }
+}
+#[cfg(feature = "elf")]
+impl From<&elf::Relro> for Mitigation {
+    fn from(relro: &elf::Relro) -> Self {
+        match relro {
+            elf::Relro::Full => Self::Enabled,
+            elf::Relro::Partial => Self::Partial,
+            elf::Relro::None => Self::Disabled,
+        }
+    }
+}
+#[cfg(feature = "elf")]
+impl From<&elf::PIE> for Mitigation {
+    fn from(pie: &elf::PIE) -> Self {
+        match pie {
+            elf::PIE::PIE | elf::PIE::DSO => Self::Enabled,
+            elf::PIE::None => Self::Disabled,
+        }
+    }
+}
+#[cfg(feature = "pe")]
+impl From<&pe::ASLR> for Mitigation {
+    fn from(aslr: &pe::ASLR) -> Self {
+        match aslr {
+            pe::ASLR::HighEntropyVa | pe::ASLR::DynamicBase => Self::Enabled,
+            // declared, but the loader can not rebase the image
+            pe::ASLR::None | pe::ASLR::NoRelocs => Self::Disabled,
+        }
+    }
+}
+
+/// Mitigations reported for ELF binaries, in output order
+#[cfg(feature = "elf")]
+pub const ELF_MITIGATIONS: [&str; 12] = [
+    "bti",
+    "canary",
+    "clang_cfi",
+    "clang_safestack",
+    "fortify",
+    "ibt",
+    "nx",
+    "pac",
+    "pie",
+    "relro",
+    "shstk",
+    "wx",
+];
+/// Mitigations reported for PE binaries, in output order
+#[cfg(feature = "pe")]
+pub const PE_MITIGATIONS: [&str; 10] = [
+    "aslr",
+    "authenticode",
+    "cfg",
+    "dep",
+    "force_integrity",
+    "gs",
+    "high_entropy_va",
+    "isolation",
+    "rfg",
+    "safeseh",
+];
+/// Mitigations reported for `MachO` binaries, in output order
+#[cfg(feature = "macho")]
+pub const MACHO_MITIGATIONS: [&str; 8] = [
+    "canary",
+    "code_signature",
+    "fortify",
+    "hardened_runtime",
+    "nx_heap",
+    "nx_stack",
+    "pie",
+    "restrict",
+];
+
+/// ELF mitigations required to consider a binary fully hardened
+#[cfg(feature = "elf")]
+const ELF_BASELINE: [&str; 5] = ["canary", "fortify", "nx", "pie", "relro"];
+/// PE mitigations required to consider a binary fully hardened
+#[cfg(feature = "pe")]
+const PE_BASELINE: [&str; 4] = ["aslr", "cfg", "dep", "gs"];
+/// `MachO` mitigations required to consider a binary fully hardened
+#[cfg(feature = "macho")]
+const MACHO_BASELINE: [&str; 3] = ["canary", "nx_stack", "pie"];
+
+/// names of all mitigations across the enabled binary formats, deduplicated
+/// and in output order
+pub fn mitigation_names() -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = Vec::new();
+    #[cfg(feature = "elf")]
+    names.extend_from_slice(&ELF_MITIGATIONS);
+    #[cfg(feature = "pe")]
+    names.extend_from_slice(&PE_MITIGATIONS);
+    #[cfg(feature = "macho")]
+    names.extend_from_slice(&MACHO_MITIGATIONS);
+    let mut seen = std::collections::HashSet::new();
+    names.retain(|name| seen.insert(*name));
+    names
+}
+
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 pub enum BinSpecificProperties {
     #[cfg(feature = "elf")]
     Elf(elf::CheckSecResults),
@@ -72,25 +218,198 @@ pub enum BinSpecificProperties {
     PE(pe::CheckSecResults),
     #[cfg(feature = "macho")]
     MachO(macho::CheckSecResults),
+    #[cfg(feature = "wasm")]
+    Wasm(wasm::CheckSecResults),
 }
 impl fmt::Display for BinSpecificProperties {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &*self {
+        match self {
             #[cfg(feature = "elf")]
             Self::Elf(b) => write!(f, "{}", b),
             #[cfg(feature = "pe")]
             Self::PE(b) => write!(f, "{}", b),
             #[cfg(feature = "macho")]
             Self::MachO(b) => write!(f, "{}", b),
+            #[cfg(feature = "wasm")]
+            Self::Wasm(b) => write!(f, "{}", b),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+impl BinSpecificProperties {
+    /// format specific supporting detail shown by verbose text output
+    pub fn evidence(&self) -> Vec<(&'static str, String)> {
+        match self {
+            #[cfg(feature = "elf")]
+            Self::Elf(elf) => elf.evidence(),
+            #[cfg(feature = "pe")]
+            Self::PE(pe) => pe.evidence(),
+            #[cfg(feature = "macho")]
+            Self::MachO(macho) => macho.evidence(),
+            #[cfg(feature = "wasm")]
+            Self::Wasm(wasm) => wasm.evidence(),
+        }
+    }
+    /// a section is likely packed or encrypted, see
+    /// [`entropy::is_packed`]
+    pub fn is_packed(&self) -> bool {
+        match self {
+            #[cfg(feature = "elf")]
+            Self::Elf(elf) => entropy::is_packed(&elf.section_entropy),
+            #[cfg(feature = "pe")]
+            Self::PE(pe) => entropy::is_packed(&pe.section_entropy),
+            #[cfg(feature = "macho")]
+            Self::MachO(macho) => entropy::is_packed(&macho.section_entropy),
+            #[cfg(feature = "wasm")]
+            Self::Wasm(_) => false,
+        }
+    }
+    /// name and state of each format specific mitigation
+    pub fn mitigations(&self) -> Vec<(&'static str, Mitigation)> {
+        match self {
+            #[cfg(feature = "elf")]
+            Self::Elf(elf) => ELF_MITIGATIONS
+                .iter()
+                .copied()
+                .zip(vec![
+                    elf.bti.into(),
+                    // Go code is never compiled with a C stack canary
+                    if !elf.canary && elf.runtime == elf::Runtime::Go {
+                        Mitigation::NotApplicable
+                    } else {
+                        elf.canary.into()
+                    },
+                    elf.clang_cfi.into(),
+                    elf.clang_safestack.into(),
+                    if elf.fortifiable == 0
+                        || !elf.fortify && elf.runtime == elf::Runtime::Go
+                    {
+                        Mitigation::NotApplicable
+                    } else {
+                        elf.fortify.into()
+                    },
+                    elf.ibt.into(),
+                    elf.nx.into(),
+                    elf.pac.into(),
+                    (&elf.pie).into(),
+                    (&elf.relro).into(),
+                    elf.shstk.into(),
+                    elf.wx_compliant.into(),
+                ])
+                .collect(),
+            #[cfg(feature = "pe")]
+            Self::PE(pe) => PE_MITIGATIONS
+                .iter()
+                .copied()
+                .zip(vec![
+                    (&pe.aslr).into(),
+                    pe.authenticode.into(),
+                    match (pe.cfg, pe.cfg_function_table) {
+                        (true, true) => Mitigation::Enabled,
+                        (true, false) => Mitigation::Partial,
+                        _ => Mitigation::Disabled,
+                    },
+                    pe.dep.into(),
+                    pe.force_integrity.into(),
+                    if pe.is_managed() {
+                        Mitigation::NotApplicable
+                    } else {
+                        // a missing security cookie can not be confirmed
+                        pe.gs.unwrap_or(false).into()
+                    },
+                    pe.high_entropy_va.into(),
+                    pe.isolation.into(),
+                    pe.rfg.into(),
+                    match pe.safeseh {
+                        _ if pe.is_managed() => Mitigation::NotApplicable,
+                        pe::SEH::NoSEH | pe::SEH::SafeSEH => {
+                            Mitigation::Enabled
+                        }
+                        pe::SEH::Unsafe => Mitigation::Disabled,
+                        pe::SEH::NotApplicable => Mitigation::NotApplicable,
+                    },
+                ])
+                .collect(),
+            #[cfg(feature = "macho")]
+            Self::MachO(macho) => MACHO_MITIGATIONS
+                .iter()
+                .copied()
+                .zip(vec![
+                    macho.canary.into(),
+                    macho.code_signature.into(),
+                    macho.fortify.into(),
+                    macho.hardened_runtime.into(),
+                    // only the flags of the main executable are honored
+                    if macho.filetype == macho::FileType::Execute {
+                        macho.nx_heap.into()
+                    } else {
+                        Mitigation::NotApplicable
+                    },
+                    if macho.filetype == macho::FileType::Execute {
+                        macho.nx_stack.into()
+                    } else {
+                        Mitigation::NotApplicable
+                    },
+                    macho.pie.into(),
+                    macho.restrict.into(),
+                ])
+                .collect(),
+            // no native mitigations apply to `WebAssembly` modules
+            #[cfg(feature = "wasm")]
+            Self::Wasm(_) => Vec::new(),
+        }
+    }
+    /// check that every baseline mitigation of the format is enabled (or not
+    /// applicable) and no known weakness is present
+    pub fn is_fully_hardened(&self) -> bool {
+        let (baseline, weak): (&[&str], bool) = match self {
+            #[cfg(feature = "elf")]
+            Self::Elf(elf) => (
+                &ELF_BASELINE,
+                elf.rwx
+                    || elf.textrel
+                    || elf.rpath.has_writable()
+                    || elf.runpath.has_writable(),
+            ),
+            #[cfg(feature = "pe")]
+            Self::PE(_) => (&PE_BASELINE, false),
+            #[cfg(feature = "macho")]
+            Self::MachO(macho) => {
+                (&MACHO_BASELINE, macho.rpath.has_writable())
+            }
+            #[cfg(feature = "wasm")]
+            Self::Wasm(_) => (&[], false),
+        };
+        !weak
+            && self.mitigations().iter().all(|(name, state)| {
+                !baseline.contains(name)
+                    || matches!(
+                        state,
+                        Mitigation::Enabled | Mitigation::NotApplicable
+                    )
+            })
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 pub struct Binary {
+    /// architecture of the slice when taken from a fat `MachO` binary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
     pub binarytype: BinType,
+    /// other files with the same contents, only set when scan results
+    /// were deduplicated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<String>,
     pub file: String,
+    /// a section is likely packed or encrypted *(heuristic, see
+    /// [`crate::entropy`])*
+    pub packed: bool,
     pub properties: BinSpecificProperties,
+    /// sha256 of the scanned file *(the whole archive for archive
+    /// members)*, only set by [`parse_path_hashed`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
 }
 #[cfg(not(feature = "color"))]
 impl fmt::Display for Binary {
@@ -99,7 +418,20 @@ impl fmt::Display for Binary {
             f,
             "{}: | {} | File: {}",
             self.binarytype, self.properties, self.file
-        )
+        )?;
+        if !self.duplicates.is_empty() {
+            write!(f, " | Duplicates: {}", self.duplicates.join(", "))?;
+        }
+        if self.packed {
+            write!(f, " | Packed: true")?;
+        }
+        // `{:#}` adds the evidence behind the results
+        if f.alternate() {
+            for (label, evidence) in self.properties.evidence() {
+                write!(f, "\n    {}: {}", label, evidence)?;
+            }
+        }
+        Ok(())
     }
 }
 #[cfg(feature = "color")]
@@ -112,46 +444,708 @@ impl fmt::Display for Binary {
             self.properties,
             "File:".bold().underline(),
             self.file.bright_blue()
-        )
+        )?;
+        if !self.duplicates.is_empty() {
+            write!(
+                f,
+                " | {} {}",
+                "Duplicates:".bold(),
+                self.duplicates.join(", ").bright_blue()
+            )?;
+        }
+        if self.packed {
+            write!(f, " | {} {}", "Packed:".bold(), "true".red())?;
+        }
+        // `{:#}` adds the evidence behind the results
+        if f.alternate() {
+            for (label, evidence) in self.properties.evidence() {
+                write!(
+                    f,
+                    "\n    {} {}",
+                    format!("{}:", label).bold(),
+                    evidence
+                )?;
+            }
+        }
+        Ok(())
     }
 }
 impl Binary {
-    pub const fn new(
+    pub fn new(
         binarytype: BinType,
         file: String,
         properties: BinSpecificProperties,
+        arch: Option<String>,
     ) -> Self {
-        Self { binarytype, file, properties }
+        Self {
+            arch,
+            binarytype,
+            duplicates: Vec::new(),
+            file,
+            packed: properties.is_packed(),
+            properties,
+            sha256: None,
+        }
+    }
+    /// drop the entropy of every section, which is only shown by verbose
+    /// output, [`Self::packed`] is kept
+    pub fn strip_section_entropy(&mut self) {
+        match &mut self.properties {
+            #[cfg(feature = "elf")]
+            BinSpecificProperties::Elf(elf) => elf.section_entropy.clear(),
+            #[cfg(feature = "pe")]
+            BinSpecificProperties::PE(pe) => pe.section_entropy.clear(),
+            #[cfg(feature = "macho")]
+            BinSpecificProperties::MachO(macho) => {
+                macho.section_entropy.clear();
+            }
+            #[cfg(feature = "wasm")]
+            BinSpecificProperties::Wasm(_) => (),
+        }
+    }
+    /// check whether the binary's format specific results are fully
+    /// hardened, see [`BinSpecificProperties::is_fully_hardened`]
+    pub fn is_fully_hardened(&self) -> bool {
+        self.properties.is_fully_hardened()
     }
 }
 
-#[derive(Deserialize, Serialize)]
+/// version of the json/yaml output layout
+///
+/// bumped whenever a field is removed, renamed or changes its type, new
+/// fields may be added without a bump
+pub const OUTPUT_VERSION: u32 = 1;
+
+/// json/yaml output of file and directory scans
+///
+/// results are read back with the same release of checksec they were
+/// written with, fields added by later releases are required
+///
+/// **Example**
+///
+/// ```rust
+/// use checksec::binary::Binaries;
+/// use checksec::parse_path;
+/// use std::path::Path;
+///
+/// let binaries = parse_path(Path::new("test/binaries/true-x86_64")).unwrap();
+/// let json = serde_json::to_string(&Binaries::new(binaries)).unwrap();
+/// let stored: Binaries = serde_json::from_str(&json).unwrap();
+/// assert_eq!(serde_json::to_string(&stored).unwrap(), json);
+/// ```
+#[derive(Deserialize, JsonSchema, Serialize)]
 pub struct Binaries {
     pub binaries: Vec<Binary>,
+    /// [`OUTPUT_VERSION`] of the layout
+    pub version: u32,
 }
 impl Binaries {
     pub fn new(binaries: Vec<Binary>) -> Self {
-        Self { binaries }
+        Self { binaries, version: OUTPUT_VERSION }
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, JsonSchema, Serialize)]
 pub struct Process {
     pub pid: usize,
+    /// every process running the same executable as `pid` (including
+    /// `pid`), only set when processes were deduplicated by executable
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pids: Vec<usize>,
     pub binary: Vec<Binary>,
 }
 impl Process {
     pub fn new(pid: usize, binary: Vec<Binary>) -> Self {
-        Self { pid, binary }
+        Self { pid, pids: Vec::new(), binary }
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, JsonSchema, Serialize)]
 pub struct Processes {
     pub processes: Vec<Process>,
+    /// [`OUTPUT_VERSION`] of the layout
+    pub version: u32,
 }
 impl Processes {
     pub fn new(processes: Vec<Process>) -> Self {
-        Self { processes }
+        Self { processes, version: OUTPUT_VERSION }
+    }
+}
+
+/// bytes peeked by goblin to detect the binary format
+const MAGIC_SIZE: usize = 16;
+
+/// Error returned when a file or buffer can not be scanned
+#[derive(Debug)]
+pub enum ParseError {
+    /// the input is empty
+    Empty,
+    /// the input is smaller than the magic of any supported format
+    TooSmall(usize),
+    /// the file could not be memory mapped and is larger than the limit
+    /// for reading it into memory
+    TooLarge(u64),
+    /// parsing took longer than a time budget of this many milliseconds,
+    /// only reported by callers enforcing one such as `--timeout` of the
+    /// command line utility
+    TimedOut(u64),
+    /// the file was modified or replaced while it was being checked, also
+    /// after reading it a second time
+    Changed,
+    /// the input could not be read or parsed by goblin
+    Goblin(Error),
+    /// some architectures of a fat `MachO` could not be parsed, `binaries`
+    /// holds the results of the remaining ones
+    FatArch { binaries: Vec<Binary>, errors: Vec<(usize, Error)> },
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty file"),
+            Self::TooSmall(size) => {
+                write!(f, "file too small to be a binary ({} bytes)", size)
+            }
+            Self::TooLarge(limit) => write!(
+                f,
+                "file can not be memory mapped and exceeds the maximum size \
+                 of {} bytes",
+                limit
+            ),
+            Self::TimedOut(timeout) => {
+                write!(f, "timed out after {} ms", timeout)
+            }
+            Self::Changed => write!(f, "file changed during scan"),
+            Self::Goblin(err) => write!(f, "{}", err),
+            Self::FatArch { errors, .. } => {
+                let errors: Vec<String> = errors
+                    .iter()
+                    .map(|(idx, err)| format!("architecture {}: {}", idx, err))
+                    .collect();
+                write!(f, "{}", errors.join(", "))
+            }
+        }
+    }
+}
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Goblin(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+impl From<Error> for ParseError {
+    fn from(err: Error) -> Self {
+        Self::Goblin(err)
+    }
+}
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        Self::Goblin(Error::IO(err))
+    }
+}
+
+/// reject inputs that can not hold any supported binary
+const fn check_size(size: usize) -> Result<(), ParseError> {
+    match size {
+        0 => Err(ParseError::Empty),
+        size if size < MAGIC_SIZE => Err(ParseError::TooSmall(size)),
+        _ => Ok(()),
+    }
+}
+
+/// parse the binary at `file` and run all checks for its format
+///
+/// fat `MachO` binaries produce one [`Binary`] per contained architecture,
+/// broken architectures are reported through [`ParseError::FatArch`]
+///
+/// # Errors
+///
+/// returns an error if the file can not be read, is too small to be a
+/// binary or is not a supported binary format, and
+/// [`ParseError::Changed`] if the file keeps changing while it is checked
+pub fn parse_path(file: &Path) -> Result<Vec<Binary>, ParseError> {
+    parse_path_with_limit(file, None)
+}
+
+/// like [`parse_path`], regular files that can not be memory mapped
+/// *(some network mounts, `/proc` entries)* are read into memory instead,
+/// up to `max_file_size` bytes
+///
+/// # Errors
+///
+/// same as [`parse_path`], additionally [`ParseError::TooLarge`] if the
+/// file had to be read into memory and is larger than `max_file_size`
+pub fn parse_path_with_limit(
+    file: &Path,
+    max_file_size: Option<u64>,
+) -> Result<Vec<Binary>, ParseError> {
+    with_contents(file, max_file_size, |name, buffer| {
+        parse_buffer(name, buffer, max_file_size, 0)
+    })
+}
+
+/// like [`parse_path_with_limit`], additionally setting
+/// [`Binary::sha256`] on every result from the same buffer the file is
+/// parsed from
+///
+/// # Errors
+///
+/// same as [`parse_path_with_limit`]
+pub fn parse_path_hashed(
+    file: &Path,
+    max_file_size: Option<u64>,
+) -> Result<Vec<Binary>, ParseError> {
+    with_contents(file, max_file_size, |name, buffer| {
+        let digest = Sha256::digest(buffer).iter().fold(
+            String::with_capacity(64),
+            |mut hex, byte| {
+                write!(hex, "{:02x}", byte).ok();
+                hex
+            },
+        );
+        let set_hash = |binaries: &mut Vec<Binary>| {
+            for binary in binaries {
+                binary.sha256 = Some(digest.clone());
+            }
+        };
+        match parse_buffer(name, buffer, max_file_size, 0) {
+            Ok(mut binaries) => {
+                set_hash(&mut binaries);
+                Ok(binaries)
+            }
+            Err(ParseError::FatArch { mut binaries, errors }) => {
+                set_hash(&mut binaries);
+                Err(ParseError::FatArch { binaries, errors })
+            }
+            Err(err) => Err(err),
+        }
+    })
+}
+
+/// directories whose files are commonly rewritten or truncated while
+/// they are scanned, they are read into memory rather than mapped
+const VOLATILE_PATHS: [&str; 5] =
+    ["/dev/shm/", "/proc/", "/run/", "/tmp/", "/var/tmp/"];
+
+/// size and modification time, compared before and after a file is
+/// checked to notice it being rewritten or replaced
+fn fingerprint(metadata: &fs::Metadata) -> (u64, Option<SystemTime>) {
+    (metadata.len(), metadata.modified().ok())
+}
+
+/// memory map `file` or read it into memory when it can not be mapped,
+/// handing its display name and contents to `parse`
+///
+/// a file that changes while it is checked is read into memory and
+/// checked once more, [`ParseError::Changed`] is returned if it changes
+/// again
+fn with_contents(
+    file: &Path,
+    max_file_size: Option<u64>,
+    parse: impl Fn(&str, &[u8]) -> Result<Vec<Binary>, ParseError>,
+) -> Result<Vec<Binary>, ParseError> {
+    let volatile = VOLATILE_PATHS
+        .iter()
+        .any(|prefix| file.to_string_lossy().starts_with(prefix));
+    match read_contents(file, max_file_size, volatile, &parse) {
+        Err(ParseError::Changed) => {
+            read_contents(file, max_file_size, true, &parse)
+        }
+        result => result,
+    }
+}
+
+/// a single attempt of [`with_contents`], `prefer_read` reads regular
+/// files within `max_file_size` into memory instead of mapping them
+///
+/// a mapped file truncated by another process raises `SIGBUS` on access,
+/// which can not be recovered from
+fn read_contents(
+    file: &Path,
+    max_file_size: Option<u64>,
+    prefer_read: bool,
+    parse: &impl Fn(&str, &[u8]) -> Result<Vec<Binary>, ParseError>,
+) -> Result<Vec<Binary>, ParseError> {
+    let mut fp = fs::File::open(file)?;
+    let metadata = fp.metadata()?;
+    let name = file.display().to_string();
+    // only regular files can be compared after the checks
+    let unchanged =
+        |result: Result<Vec<Binary>, ParseError>| match fs::metadata(file) {
+            Ok(after)
+                if metadata.is_file()
+                    && fingerprint(&after) != fingerprint(&metadata) =>
+            {
+                Err(ParseError::Changed)
+            }
+            _ => result,
+        };
+    let read = prefer_read
+        && metadata.is_file()
+        && max_file_size.is_none_or(|limit| metadata.len() <= limit);
+    // mapping an empty file fails, `/proc` entries report a size of 0
+    // but still have contents to read
+    if !read && (metadata.len() > 0 || !metadata.is_file()) {
+        #[allow(clippy::cast_possible_truncation)]
+        check_size(metadata.len() as usize)?;
+        match unsafe { Mmap::map(&fp) } {
+            Ok(buffer) => return unchanged(parse(&name, &buffer)),
+            // reading devices or pipes could block or never end
+            Err(err) if !metadata.is_file() => return Err(err.into()),
+            Err(_) => (),
+        }
+    }
+    let mut buffer: Vec<u8> = Vec::new();
+    match max_file_size {
+        Some(limit) => {
+            fp.take(limit.saturating_add(1)).read_to_end(&mut buffer)?;
+            if buffer.len() as u64 > limit {
+                return Err(ParseError::TooLarge(limit));
+            }
+        }
+        None => {
+            fp.read_to_end(&mut buffer)?;
+        }
+    }
+    unchanged(parse(&name, &buffer))
+}
+
+/// local file header signature at the start of zip archives
+#[cfg(feature = "zip")]
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// largest zip entry decompressed into memory without a `max_file_size`
+#[cfg(feature = "zip")]
+const ZIP_ENTRY_MAX_SIZE: u64 = 256 << 20;
+/// archives nested deeper than this are not opened, a zip containing
+/// itself would otherwise recurse forever
+#[cfg(feature = "zip")]
+const ZIP_MAX_DEPTH: usize = 2;
+
+/// parse every entry of a zip archive (apk, jar, ...) into memory,
+/// reporting binaries as `bundle.zip!/path/in/archive`
+///
+/// entries that are not binaries of a supported format or that are larger
+/// than `max_file_size` *(or [`ZIP_ENTRY_MAX_SIZE`])* once decompressed
+/// are skipped, `depth` counts the archives this one is nested in
+#[cfg(feature = "zip")]
+fn parse_zip(
+    file: &str,
+    buffer: &[u8],
+    max_file_size: Option<u64>,
+    depth: usize,
+) -> Result<Vec<Binary>, ParseError> {
+    let limit = max_file_size.unwrap_or(ZIP_ENTRY_MAX_SIZE);
+    let mut archive = zip::ZipArchive::new(io::Cursor::new(buffer))
+        .map_err(|err| Error::Malformed(format!("invalid zip: {}", err)))?;
+    let mut bins: Vec<Binary> = Vec::new();
+    for idx in 0..archive.len() {
+        let entry = match archive.by_index(idx) {
+            Ok(entry) if entry.is_file() => entry,
+            _ => continue,
+        };
+        if entry.size() > limit {
+            continue;
+        }
+        let name = format!("{}!/{}", file, entry.name());
+        // the size in the header is untrusted, a zip bomb decompresses
+        // to far more than it declares
+        let mut data: Vec<u8> = Vec::new();
+        if entry.take(limit.saturating_add(1)).read_to_end(&mut data).is_err()
+            || data.len() as u64 > limit
+        {
+            continue;
+        }
+        match parse_buffer(&name, &data, max_file_size, depth + 1) {
+            Ok(mut results)
+            | Err(ParseError::FatArch { binaries: mut results, .. }) => {
+                bins.append(&mut results);
+            }
+            Err(_) => (),
+        }
+    }
+    Ok(bins)
+}
+
+/// parse an in-memory binary, reporting it as `file`
+///
+/// # Errors
+///
+/// returns an error if `buffer` is too small to be a binary or is not a
+/// supported binary format
+pub fn parse_bytes(
+    file: &str,
+    buffer: &[u8],
+) -> Result<Vec<Binary>, ParseError> {
+    parse_buffer(file, buffer, None, 0)
+}
+
+/// [`parse_bytes`] of a buffer nested in `depth` zip archives, whose
+/// entries are decompressed up to `max_file_size` bytes
+#[cfg_attr(not(feature = "zip"), allow(clippy::only_used_in_recursion))]
+fn parse_buffer(
+    file: &str,
+    buffer: &[u8],
+    max_file_size: Option<u64>,
+    depth: usize,
+) -> Result<Vec<Binary>, ParseError> {
+    check_size(buffer.len())?;
+    #[cfg(feature = "zip")]
+    if buffer.starts_with(ZIP_MAGIC) {
+        if depth >= ZIP_MAX_DEPTH {
+            return Err(ParseError::Goblin(Error::Malformed(
+                "zip archives nested too deeply".to_string(),
+            )));
+        }
+        return parse_zip(file, buffer, max_file_size, depth);
+    }
+    // modules of other versions are reported as not valid
+    #[cfg(feature = "wasm")]
+    if buffer.starts_with(&wasm::WASM_MAGIC[..4]) {
+        return Ok(vec![Binary::new(
+            BinType::Wasm,
+            file.to_string(),
+            BinSpecificProperties::Wasm(wasm::CheckSecResults::parse(buffer)),
+            None,
+        )]);
+    }
+    match Object::parse(buffer)? {
+        #[cfg(feature = "elf")]
+        Object::Elf(elf) => {
+            let results = elf::CheckSecResults::parse(&elf, buffer);
+            let bin_type =
+                if elf.is_64 { BinType::Elf64 } else { BinType::Elf32 };
+            Ok(vec![Binary::new(
+                bin_type,
+                file.to_string(),
+                BinSpecificProperties::Elf(results),
+                None,
+            )])
+        }
+        #[cfg(feature = "pe")]
+        Object::PE(pe) => {
+            let results = pe::CheckSecResults::parse(&pe, buffer);
+            let bin_type =
+                if pe.is_64 { BinType::PE64 } else { BinType::PE32 };
+            Ok(vec![Binary::new(
+                bin_type,
+                file.to_string(),
+                BinSpecificProperties::PE(results),
+                None,
+            )])
+        }
+        #[cfg(feature = "macho")]
+        Object::Mach(mach) => match mach {
+            Mach::Binary(macho) => {
+                let results = macho::CheckSecResults::parse(&macho, buffer);
+                let bin_type = if macho.is_64 {
+                    BinType::MachO64
+                } else {
+                    BinType::MachO32
+                };
+                Ok(vec![Binary::new(
+                    bin_type,
+                    file.to_string(),
+                    BinSpecificProperties::MachO(results),
+                    None,
+                )])
+            }
+            Mach::Fat(fatmach) => {
+                let mut fat_bins: Vec<Binary> = Vec::new();
+                let mut errors: Vec<(usize, Error)> = Vec::new();
+                for (idx, arch) in fatmach.iter_arches().enumerate() {
+                    let arch = match arch {
+                        Ok(arch) => arch,
+                        Err(err) => {
+                            errors.push((idx, err));
+                            continue;
+                        }
+                    };
+                    // goblin slices the buffer without bounds checks
+                    if (arch.offset as usize)
+                        .checked_add(arch.size as usize)
+                        .is_none_or(|end| end > buffer.len())
+                    {
+                        errors.push((
+                            idx,
+                            Error::Malformed(
+                                "architecture extends past the end of the \
+                                 file"
+                                    .to_string(),
+                            ),
+                        ));
+                        continue;
+                    }
+                    let container: MachO = match fatmach.get(idx) {
+                        Ok(container) => container,
+                        Err(err) => {
+                            errors.push((idx, err));
+                            continue;
+                        }
+                    };
+                    let slice =
+                        &buffer[arch.offset as usize..][..arch.size as usize];
+                    let results =
+                        macho::CheckSecResults::parse(&container, slice);
+                    let bin_type = if container.is_64 {
+                        BinType::MachO64
+                    } else {
+                        BinType::MachO32
+                    };
+                    let arch = get_arch_name_from_types(
+                        container.header.cputype(),
+                        container.header.cpusubtype(),
+                    );
+                    fat_bins.append(&mut vec![Binary::new(
+                        bin_type,
+                        file.to_string(),
+                        BinSpecificProperties::MachO(results),
+                        arch.map(str::to_string),
+                    )]);
+                }
+                if errors.is_empty() {
+                    Ok(fat_bins)
+                } else {
+                    Err(ParseError::FatArch { binaries: fat_bins, errors })
+                }
+            }
+        },
+        // static libraries, every member object of a supported format is
+        // reported as `archive.a(member.o)`
+        Object::Archive(archive) => {
+            let mut bins: Vec<Binary> = Vec::new();
+            for member in archive.members() {
+                let data = match archive.extract(member, buffer) {
+                    Ok(data) => data,
+                    Err(_) => continue,
+                };
+                match parse_buffer(
+                    &format!("{}({})", file, member),
+                    data,
+                    max_file_size,
+                    depth,
+                ) {
+                    Ok(mut results)
+                    | Err(ParseError::FatArch {
+                        binaries: mut results, ..
+                    }) => {
+                        bins.append(&mut results);
+                    }
+                    // symbol tables and objects of unsupported formats
+                    Err(_) => (),
+                }
+            }
+            Ok(bins)
+        }
+        _ => Err(Error::BadMagic(0).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_file() {
+        let file = std::env::temp_dir()
+            .join(format!("checksec-empty-{}", std::process::id()));
+        fs::File::create(&file).unwrap();
+        let parse = |name: &str, buffer: &[u8]| parse_bytes(name, buffer);
+        // mapped and read into memory
+        let results = [
+            read_contents(&file, None, false, &parse),
+            read_contents(&file, None, true, &parse),
+        ];
+        fs::remove_file(&file).unwrap();
+        for result in results {
+            assert!(matches!(result, Err(ParseError::Empty)));
+        }
+    }
+
+    /// fat `MachO` with a valid x86_64 slice and an arm64 one pointing
+    /// past the end of the file
+    #[cfg(feature = "macho")]
+    fn fat_with_broken_arch() -> Vec<u8> {
+        // `mach_header_64` of an x86_64 PIE executable without load commands
+        let mut slice = Vec::new();
+        for word in [0xfeed_facf_u32, 0x0100_0007, 3, 2, 0, 0, 0x0020_0000, 0]
+        {
+            slice.extend_from_slice(&word.to_le_bytes());
+        }
+        // two `fat_arch` entries
+        let mut fat = Vec::new();
+        for word in [
+            0xcafe_babe_u32,
+            2,
+            0x0100_0007,
+            3,
+            64,
+            32,
+            0,
+            0x0100_000c,
+            0,
+            0x1_0000,
+            32,
+            0,
+        ] {
+            fat.extend_from_slice(&word.to_be_bytes());
+        }
+        fat.resize(64, 0);
+        fat.extend_from_slice(&slice);
+        fat
+    }
+
+    #[cfg(feature = "macho")]
+    #[test]
+    fn fat_arch_out_of_range() {
+        match parse_bytes("fat", &fat_with_broken_arch()) {
+            Err(ParseError::FatArch { binaries, errors }) => {
+                assert_eq!(binaries.len(), 1);
+                assert_eq!(binaries[0].arch.as_deref(), Some("x86_64"));
+                assert!(matches!(
+                    binaries[0].properties,
+                    BinSpecificProperties::MachO(ref macho) if macho.pie
+                ));
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].0, 1);
+            }
+            other => panic!("expected ParseError::FatArch, got {:?}", other),
+        }
+    }
+    /// results of `true-x86_64` followed by the slice of
+    /// [`fat_with_broken_arch`]
+    #[cfg(all(feature = "elf", feature = "macho"))]
+    fn elf_and_fat() -> Binaries {
+        let mut binaries =
+            parse_path(Path::new("test/binaries/true-x86_64")).unwrap();
+        match parse_bytes("fat", &fat_with_broken_arch()) {
+            Err(ParseError::FatArch { binaries: mut fat, .. }) => {
+                binaries.append(&mut fat);
+            }
+            other => panic!("expected ParseError::FatArch, got {:?}", other),
+        }
+        Binaries::new(binaries)
+    }
+
+    #[cfg(all(feature = "elf", feature = "macho"))]
+    #[test]
+    fn json_round_trip() {
+        let json = serde_json::to_string(&elf_and_fat()).unwrap();
+        let stored: Binaries = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&stored).unwrap(), json);
+    }
+
+    #[cfg(all(feature = "elf", feature = "macho"))]
+    #[test]
+    fn yaml_round_trip() {
+        let binaries = elf_and_fat();
+        let yaml = serde_yaml::to_string(&binaries).unwrap();
+        let stored: Binaries = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(serde_yaml::to_string(&stored).unwrap(), yaml);
+        assert_eq!(
+            serde_json::to_string(&stored).unwrap(),
+            serde_json::to_string(&binaries).unwrap()
+        );
     }
 }
@@ -0,0 +1,141 @@
+//! Results of earlier scans kept in the file given with `--cache`
+//!
+//! a file is parsed again when its size or modification time changed, the
+//! cache only holds the files checked by the last run using it
+use checksec::binary::Binary;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::SystemTime;
+
+/// results of one file and what they were taken from
+#[derive(Deserialize, Serialize)]
+struct Entry {
+    /// results are the serialized [`Binary`] list, kept as a json value so
+    /// unused entries are not deserialized
+    binaries: Value,
+    /// canonical path, a relative path seen from another directory is a
+    /// different file
+    canonical: PathBuf,
+    /// sha256 of the contents is part of the results
+    hashed: bool,
+    modified: SystemTime,
+    size: u64,
+}
+
+/// what is on disk, results of other checksec releases are discarded
+#[derive(Default, Deserialize, Serialize)]
+struct Store {
+    entries: HashMap<String, Entry>,
+    version: String,
+}
+
+pub struct Cache {
+    /// entries read from the cache file, moved to `current` when used
+    previous: Mutex<HashMap<String, Entry>>,
+    /// entries written back after the scan
+    current: Mutex<HashMap<String, Entry>>,
+    path: PathBuf,
+}
+
+/// canonical path, size and modification time `file` is cached under
+fn stamp(file: &Path) -> Option<(PathBuf, u64, SystemTime)> {
+    let metadata = fs::metadata(file).ok()?;
+    Some((
+        file.canonicalize().ok()?,
+        metadata.len(),
+        metadata.modified().ok()?,
+    ))
+}
+
+impl Cache {
+    /// read the cache at `path`, starting empty when it does not exist yet
+    /// or was written by another release
+    pub fn load(path: &str) -> Self {
+        let store = match fs::read(path) {
+            Ok(contents) => serde_json::from_slice::<Store>(&contents)
+                .unwrap_or_else(|err| {
+                    eprintln!("Ignoring unreadable cache {}: {}", path, err);
+                    Store::default()
+                }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Store::default()
+            }
+            Err(err) => {
+                eprintln!("Can not read cache {}: {}", path, err);
+                Store::default()
+            }
+        };
+        let entries = if store.version == env!("CARGO_PKG_VERSION") {
+            store.entries
+        } else {
+            HashMap::new()
+        };
+        Self {
+            previous: Mutex::new(entries),
+            current: Mutex::new(HashMap::new()),
+            path: PathBuf::from(path),
+        }
+    }
+    /// cached results of `file` when it is unchanged since they were
+    /// stored, results without a sha256 do not satisfy a `hashed` lookup
+    pub fn get(&self, file: &Path, hashed: bool) -> Option<Vec<Binary>> {
+        let key = file.display().to_string();
+        let (canonical, size, modified) = stamp(file)?;
+        let entry = self
+            .previous
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&key)?;
+        if entry.canonical != canonical
+            || entry.size != size
+            || entry.modified != modified
+            || (hashed && !entry.hashed)
+        {
+            return None;
+        }
+        let binaries = Vec::<Binary>::deserialize(&entry.binaries).ok()?;
+        self.current
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, entry);
+        Some(binaries)
+    }
+    /// store the results of `file` as parsed just now
+    pub fn insert(&self, file: &Path, hashed: bool, binaries: &[Binary]) {
+        let (canonical, size, modified) = match stamp(file) {
+            Some(stamp) => stamp,
+            None => return,
+        };
+        let binaries = match serde_json::to_value(binaries) {
+            Ok(binaries) => binaries,
+            Err(_) => return,
+        };
+        self.current.lock().unwrap_or_else(PoisonError::into_inner).insert(
+            file.display().to_string(),
+            Entry { binaries, canonical, hashed, modified, size },
+        );
+    }
+    /// write the entries used by this run back to the cache file
+    pub fn save(self) -> io::Result<()> {
+        let store = Store {
+            entries: self
+                .current
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        let contents = serde_json::to_vec(&store).map_err(io::Error::other)?;
+        // written next to the cache and renamed, an interrupted run keeps
+        // the previous cache intact
+        let mut partial = self.path.clone().into_os_string();
+        partial.push(".partial");
+        fs::write(&partial, contents)?;
+        fs::rename(&partial, &self.path)
+    }
+}
@@ -0,0 +1,82 @@
+use checksec::binary::{mitigation_names, Binary, Mitigation};
+
+use crate::fields;
+use crate::output::outln;
+
+/// leading columns of every csv row, followed by one column per mitigation
+/// in [`mitigation_names`] order
+const LEADING_COLUMNS: [&str; 3] = ["file", "type", "arch"];
+
+fn cell(state: Mitigation) -> &'static str {
+    match state {
+        Mitigation::Enabled => "yes",
+        Mitigation::Partial => "partial",
+        Mitigation::Disabled => "no",
+        Mitigation::NotApplicable => "na",
+    }
+}
+
+/// quote a field if it contains a separator, quote or line break
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// stable header row, independent of the scanned binaries
+pub fn header() -> String {
+    LEADING_COLUMNS
+        .iter()
+        .copied()
+        .chain(mitigation_names())
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
+/// csv row for a single binary, mitigations that do not apply to its
+/// format are reported as `na`
+pub fn row(binary: &Binary) -> String {
+    let mitigations = binary.properties.mitigations();
+    let mut fields = vec![
+        escape(&binary.file),
+        format!("{:?}", binary.binarytype),
+        binary.arch.as_deref().map(escape).unwrap_or_default(),
+    ];
+    fields.extend(
+        mitigation_names()
+            .iter()
+            .map(|name| {
+                mitigations
+                    .iter()
+                    .find(|(mitigation, _)| mitigation == name)
+                    .map_or(Mitigation::NotApplicable, |(_, state)| *state)
+            })
+            .map(|state| cell(state).to_string()),
+    );
+    fields.join(",")
+}
+
+pub fn print<'a>(bins: impl IntoIterator<Item = &'a Binary>) {
+    outln!("{}", header());
+    for bin in bins {
+        outln!("{}", row(bin));
+    }
+}
+
+/// csv with one column per selected field, fields that do not apply to
+/// the format of a binary are left empty
+pub fn print_fields<'a>(
+    bins: impl IntoIterator<Item = &'a Binary>,
+    selected: &[String],
+) {
+    outln!("{}", selected.join(","));
+    for bin in bins {
+        let row: Vec<String> = fields::values(bin, selected)
+            .iter()
+            .map(|value| value.as_deref().map(escape).unwrap_or_default())
+            .collect();
+        outln!("{}", row.join(","));
+    }
+}
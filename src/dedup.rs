@@ -0,0 +1,37 @@
+use checksec::binary::Binary;
+
+use std::collections::HashMap;
+
+/// keep the results of the first file with a given
+/// [`Binary::sha256`], listing the paths of later files with the same
+/// contents in [`Binary::duplicates`]
+///
+/// `files` holds the path and results of every scanned file in order,
+/// results without a hash are always kept
+pub fn dedup(files: Vec<(String, Vec<Binary>)>) -> Vec<Binary> {
+    let mut unique: Vec<(Vec<Binary>, Vec<String>)> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (file, binaries) in files {
+        let hash = binaries.first().and_then(|bin| bin.sha256.clone());
+        match hash {
+            Some(hash) => {
+                if let Some(&idx) = seen.get(&hash) {
+                    unique[idx].1.push(file);
+                } else {
+                    seen.insert(hash, unique.len());
+                    unique.push((binaries, Vec::new()));
+                }
+            }
+            None => unique.push((binaries, Vec::new())),
+        }
+    }
+    unique
+        .into_iter()
+        .flat_map(|(binaries, duplicates)| {
+            binaries.into_iter().map(move |mut bin| {
+                bin.duplicates = duplicates.clone();
+                bin
+            })
+        })
+        .collect()
+}
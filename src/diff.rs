@@ -0,0 +1,98 @@
+use checksec::binary::{Binary, Mitigation};
+
+use crate::output::outln;
+
+fn label(state: Mitigation) -> &'static str {
+    match state {
+        Mitigation::Enabled => "yes",
+        Mitigation::Partial => "partial",
+        Mitigation::Disabled => "no",
+        Mitigation::NotApplicable => "na",
+    }
+}
+
+/// ordering of mitigation states, `None` for states that can not be
+/// compared
+fn strength(state: Mitigation) -> Option<u8> {
+    match state {
+        Mitigation::Enabled => Some(2),
+        Mitigation::Partial => Some(1),
+        Mitigation::Disabled => Some(0),
+        Mitigation::NotApplicable => None,
+    }
+}
+
+/// mitigations that differ between two binaries as `name: old → new`
+/// lines, along with whether any of them got weaker
+fn compare(old: &Binary, new: &Binary) -> (Vec<String>, bool) {
+    let new_mitigations = new.properties.mitigations();
+    let mut lines: Vec<String> = Vec::new();
+    let mut regressed = false;
+    for (name, before) in old.properties.mitigations() {
+        let after = new_mitigations
+            .iter()
+            .find(|(mitigation, _)| *mitigation == name)
+            .map_or(Mitigation::NotApplicable, |(_, state)| *state);
+        if before == after {
+            continue;
+        }
+        if let (Some(before), Some(after)) =
+            (strength(before), strength(after))
+        {
+            regressed |= after < before;
+        }
+        lines.push(format!(
+            "{}: {} \u{2192} {}",
+            name,
+            label(before),
+            label(after)
+        ));
+    }
+    (lines, regressed)
+}
+
+/// print the mitigations that changed from the `old` to the `new` build,
+/// returning whether any mitigation regressed
+///
+/// slices of fat `MachO` binaries are paired up by architecture
+pub fn print(old: &[Binary], new: &[Binary]) -> bool {
+    let fat = old.iter().chain(new).any(|bin| bin.arch.is_some());
+    let mut regressed = false;
+    if !fat {
+        if let (Some(old), Some(new)) = (old.first(), new.first()) {
+            let (lines, weaker) = compare(old, new);
+            for line in lines {
+                outln!("{}", line);
+            }
+            regressed |= weaker;
+        }
+        return regressed;
+    }
+    for old_bin in old {
+        let arch = old_bin.arch.as_deref().unwrap_or("unknown");
+        match new.iter().find(|bin| bin.arch == old_bin.arch) {
+            Some(new_bin) => {
+                let (lines, weaker) = compare(old_bin, new_bin);
+                for line in lines {
+                    outln!("[{}] {}", arch, line);
+                }
+                regressed |= weaker;
+            }
+            None => {
+                outln!("[{}] only in {}", arch, old_bin.file);
+                // dropping an architecture loses all of its mitigations
+                regressed = true;
+            }
+        }
+    }
+    for new_bin in new {
+        if !old.iter().any(|bin| bin.arch == new_bin.arch) {
+            outln!(
+                "[{}] only in {}",
+                new_bin.arch.as_deref().unwrap_or("unknown"),
+                new_bin.file
+            );
+        }
+    }
+    regressed
+}
@@ -2,20 +2,134 @@
 #[cfg(feature = "color")]
 use colored::Colorize;
 use goblin::elf::dynamic::{
-    DF_1_NOW, DF_1_PIE, DF_BIND_NOW, DT_RPATH, DT_RUNPATH,
+    DF_1_NODEFLIB, DF_1_NODELETE, DF_1_NOOPEN, DF_1_NOW, DF_1_ORIGIN,
+    DF_1_PIE, DF_BIND_NOW, DF_ORIGIN, DF_STATIC_TLS, DF_SYMBOLIC, DF_TEXTREL,
+    DT_BIND_NOW, DT_RPATH, DT_RUNPATH, DT_TEXTREL,
 };
-use goblin::elf::header::ET_DYN;
-use goblin::elf::program_header::{PF_X, PT_GNU_RELRO, PT_GNU_STACK};
+use goblin::elf::header::{
+    machine_to_str, EI_OSABI, ELFOSABI_NONE, EM_386, EM_AARCH64, EM_ARM,
+    EM_MIPS, EM_PPC, EM_PPC64, EM_RISCV, EM_S390, EM_SPARC, EM_SPARCV9,
+    EM_X86_64, ET_DYN,
+};
+use goblin::elf::note::{Note, NT_GNU_BUILD_ID};
+use goblin::elf::program_header::{
+    PF_R, PF_W, PF_X, PT_GNU_RELRO, PT_GNU_STACK, PT_LOAD,
+};
+use goblin::elf::reloc::{
+    R_386_IRELATIVE, R_AARCH64_IRELATIVE, R_ARM_IRELATIVE, R_X86_64_IRELATIVE,
+};
+use goblin::elf::section_header::SHT_SYMTAB;
+use goblin::elf::sym::STT_GNU_IFUNC;
 use goblin::elf::Elf;
+use schemars::JsonSchema;
+use scroll::Pread;
 use serde_derive::{Deserialize, Serialize};
+use std::convert::TryFrom;
 use std::fmt;
 
-#[cfg(feature = "color")]
-use crate::colorize_bool;
+use crate::entropy::{self, SectionEntropy};
+#[cfg(not(feature = "color"))]
+use crate::option_bool;
 use crate::shared::{Rpath, VecRpath};
+#[cfg(feature = "color")]
+use crate::{colorize_bool, colorize_bool_inverse, colorize_option_bool};
+
+const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc000_0000;
+const GNU_PROPERTY_AARCH64_FEATURE_1_BTI: u32 = 0x0000_0001;
+const GNU_PROPERTY_AARCH64_FEATURE_1_PAC: u32 = 0x0000_0002;
+const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc000_0002;
+const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 0x0000_0001;
+const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 0x0000_0002;
+
+/// libc functions with a `__*_chk` fortified variant
+const FORTIFIABLE_FUNCTIONS: [&str; 79] = [
+    "asprintf",
+    "confstr",
+    "dprintf",
+    "explicit_bzero",
+    "fdelt",
+    "fgets",
+    "fgets_unlocked",
+    "fgetws",
+    "fgetws_unlocked",
+    "fprintf",
+    "fread",
+    "fread_unlocked",
+    "fwprintf",
+    "getcwd",
+    "getdomainname",
+    "getgroups",
+    "gethostname",
+    "getlogin_r",
+    "gets",
+    "getwd",
+    "longjmp",
+    "mbsnrtowcs",
+    "mbsrtowcs",
+    "mbstowcs",
+    "memcpy",
+    "memmove",
+    "mempcpy",
+    "memset",
+    "obstack_printf",
+    "obstack_vprintf",
+    "poll",
+    "ppoll",
+    "pread",
+    "pread64",
+    "printf",
+    "ptsname_r",
+    "read",
+    "readlink",
+    "readlinkat",
+    "realpath",
+    "recv",
+    "recvfrom",
+    "snprintf",
+    "sprintf",
+    "stpcpy",
+    "stpncpy",
+    "strcat",
+    "strcpy",
+    "strlcat",
+    "strlcpy",
+    "strncat",
+    "strncpy",
+    "swprintf",
+    "syslog",
+    "ttyname_r",
+    "vasprintf",
+    "vdprintf",
+    "vfprintf",
+    "vfwprintf",
+    "vprintf",
+    "vsnprintf",
+    "vsprintf",
+    "vswprintf",
+    "vsyslog",
+    "vwprintf",
+    "wcpcpy",
+    "wcpncpy",
+    "wcrtomb",
+    "wcscat",
+    "wcscpy",
+    "wcsncat",
+    "wcsncpy",
+    "wcsnrtombs",
+    "wcsrtombs",
+    "wcstombs",
+    "wctomb",
+    "wmemcpy",
+    "wmemmove",
+    "wprintf",
+];
 
 /// Relocation Read-Only mode: `None`, `Partial`, or `Full`
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+///
+/// displayed as `No RELRO`, `Partial RELRO` and `Full RELRO`, json and yaml
+/// output keep the variant names of earlier releases
+#[derive(Debug, Deserialize, JsonSchema, Serialize, PartialEq)]
 pub enum Relro {
     None,
     Partial,
@@ -27,11 +141,11 @@ impl fmt::Display for Relro {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{:<7}",
+            "{:<13}",
             match *self {
-                Self::None => "None",
-                Self::Partial => "Partial",
-                Self::Full => "Full",
+                Self::None => "No RELRO",
+                Self::Partial => "Partial RELRO",
+                Self::Full => "Full RELRO",
             }
         )
     }
@@ -39,18 +153,21 @@ impl fmt::Display for Relro {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{:<7}",
+            "{:<13}",
             match *self {
-                Self::None => "None".red(),
-                Self::Partial => "Partial".yellow(),
-                Self::Full => "Full".green(),
+                Self::None => "No RELRO".red(),
+                Self::Partial => "Partial RELRO".yellow(),
+                Self::Full => "Full RELRO".green(),
             }
         )
     }
 }
 
 /// Position Independent Executable mode: `None`, `DSO`, or `PIE`
-#[derive(Debug, Deserialize, Serialize)]
+///
+/// `PIE` is an `ET_DYN` executable, `DSO` a shared library and `None` an
+/// `ET_EXEC` (position dependent) executable
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 pub enum PIE {
     None,
     DSO,
@@ -62,11 +179,11 @@ impl fmt::Display for PIE {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{:<4}",
+            "{:<6}",
             match *self {
-                Self::None => "None",
+                Self::None => "No PIE",
                 Self::DSO => "DSO",
-                Self::PIE => "Full",
+                Self::PIE => "PIE",
             }
         )
     }
@@ -74,16 +191,334 @@ impl fmt::Display for PIE {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{:<4}",
+            "{:<6}",
             match *self {
-                Self::None => "None".red(),
+                Self::None => "No PIE".red(),
                 Self::DSO => "DSO".yellow(),
-                Self::PIE => "Full".green(),
+                Self::PIE => "PIE".green(),
+            }
+        )
+    }
+}
+
+/// How an ELF binary is linked
+///
+/// static-pie binaries are `ET_DYN` without a `PT_INTERP` program header
+/// or any `DT_NEEDED` library, marked with `DF_1_PIE` or carrying the
+/// `_dl_relocate_static_pie` startup code, they relocate themselves at
+/// startup
+#[derive(Debug, Deserialize, PartialEq, JsonSchema, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Linkage {
+    Dynamic,
+    Static,
+    StaticPie,
+}
+
+impl fmt::Display for Linkage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<10}",
+            match *self {
+                Self::Dynamic => "dynamic",
+                Self::Static => "static",
+                Self::StaticPie => "static-pie",
+            }
+        )
+    }
+}
+
+/// Language runtime the binary was built with
+///
+/// Go compiles its own code without a C stack canary or fortified libc
+/// calls, Rust brings its own bounds checks, so the canary and fortify
+/// results only describe linked C code
+#[derive(Debug, Deserialize, PartialEq, JsonSchema, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    Go,
+    Rust,
+    Unknown,
+}
+
+impl fmt::Display for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match *self {
+                Self::Go => "go",
+                Self::Rust => "rust",
+                Self::Unknown => "unknown",
+            }
+        )
+    }
+}
+
+// `EI_OSABI` values of the BSDs
+const ELFOSABI_NETBSD: u8 = 2;
+const ELFOSABI_FREEBSD: u8 = 9;
+const ELFOSABI_OPENBSD: u8 = 12;
+/// `NT_FREEBSD_ABI_TAG`, `NT_NETBSD_IDENT` and `NT_OPENBSD_IDENT`
+const NT_BSD_IDENT: u32 = 1;
+const NT_NETBSD_PAX: u32 = 3;
+const NT_FREEBSD_FEATURE_CTL: u32 = 4;
+
+/// OpenBSD program header types
+const OPENBSD_PROGRAM_HEADERS: [(u32, &str); 6] = [
+    (0x65a3_dbe5, "PT_OPENBSD_MUTABLE"),
+    (0x65a3_dbe6, "PT_OPENBSD_RANDOMIZE"),
+    (0x65a3_dbe7, "PT_OPENBSD_WXNEEDED"),
+    (0x65a3_dbe8, "PT_OPENBSD_NOBTCFI"),
+    (0x65a3_dbe9, "PT_OPENBSD_SYSCALLS"),
+    (0x65a4_1be6, "PT_OPENBSD_BOOTDATA"),
+];
+const PT_OPENBSD_WXNEEDED: u32 = 0x65a3_dbe7;
+/// flags of the NetBSD `PaX` note
+const NETBSD_PAX_FLAGS: [(u32, &str); 6] = [
+    (0x01, "PAX_MPROTECT"),
+    (0x02, "PAX_NOMPROTECT"),
+    (0x04, "PAX_GUARD"),
+    (0x08, "PAX_NOGUARD"),
+    (0x10, "PAX_ASLR"),
+    (0x20, "PAX_NOASLR"),
+];
+const NT_NETBSD_PAX_NOMPROTECT: u32 = 0x02;
+const NT_NETBSD_PAX_NOASLR: u32 = 0x20;
+/// flags of the FreeBSD feature control note, set by `elfctl`
+const FREEBSD_FCTL_FLAGS: [(u32, &str); 5] = [
+    (0x01, "FCTL_ASLR_DISABLE"),
+    (0x02, "FCTL_PROTMAX_DISABLE"),
+    (0x04, "FCTL_STKGAP_DISABLE"),
+    (0x08, "FCTL_WXNEEDED"),
+    (0x10, "FCTL_LA48"),
+];
+const NT_FREEBSD_FCTL_ASLR_DISABLE: u32 = 0x01;
+const NT_FREEBSD_FCTL_WXNEEDED: u32 = 0x08;
+
+/// BSD the binary was built for
+#[derive(Debug, Deserialize, PartialEq, JsonSchema, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BsdOs {
+    FreeBSD,
+    NetBSD,
+    OpenBSD,
+}
+
+impl fmt::Display for BsdOs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match *self {
+                Self::FreeBSD => "FreeBSD",
+                Self::NetBSD => "NetBSD",
+                Self::OpenBSD => "OpenBSD",
             }
         )
     }
 }
 
+/// Platform mitigation markers of FreeBSD, NetBSD and OpenBSD binaries
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct Bsd {
+    /// ASLR is opted out of (`FCTL_ASLR_DISABLE` or `PAX_NOASLR`)
+    pub aslr_disabled: bool,
+    /// OpenBSD program header types, NetBSD `PaX` flags and FreeBSD
+    /// feature control flags present, e.g. `PT_OPENBSD_WXNEEDED`
+    pub markers: Vec<String>,
+    /// from `EI_OSABI`, or the ident note when it is left at System V
+    pub os: BsdOs,
+    /// W^X is not enforced at run time (`PT_OPENBSD_WXNEEDED`,
+    /// `FCTL_WXNEEDED` or `PAX_NOMPROTECT`)
+    pub wxneeded: bool,
+}
+
+/// names of the `flags` set in a note descriptor
+fn flag_names(flags: u32, names: &[(u32, &'static str)]) -> Vec<String> {
+    names
+        .iter()
+        .filter(|(flag, _)| flags & flag != 0)
+        .map(|(_, name)| (*name).to_string())
+        .collect()
+}
+
+/// Decoded `DT_FLAGS`/`DT_FLAGS_1` entries of the `PT_DYNAMIC` program
+/// header
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct DynamicFlags {
+    /// `DF_BIND_NOW` in `DT_FLAGS`
+    pub bind_now: bool,
+    /// `DF_1_NODEFLIB` in `DT_FLAGS_1`, default library paths are ignored
+    pub nodeflib: bool,
+    /// `DF_1_NODELETE` in `DT_FLAGS_1`, the object is never unloaded
+    pub nodelete: bool,
+    /// `DF_1_NOOPEN` in `DT_FLAGS_1`, the object can not be `dlopen`ed
+    pub noopen: bool,
+    /// `DF_1_NOW` in `DT_FLAGS_1`
+    pub now: bool,
+    /// `DF_ORIGIN` in `DT_FLAGS` or `DF_1_ORIGIN` in `DT_FLAGS_1`
+    pub origin: bool,
+    /// `DF_1_PIE` in `DT_FLAGS_1`
+    pub pie: bool,
+    /// `DF_STATIC_TLS` in `DT_FLAGS`
+    pub static_tls: bool,
+    /// `DF_SYMBOLIC` in `DT_FLAGS`
+    pub symbolic: bool,
+    /// `DF_TEXTREL` in `DT_FLAGS`
+    pub textrel: bool,
+}
+impl DynamicFlags {
+    /// names of the flags that are set
+    #[must_use]
+    pub fn names(&self) -> Vec<&'static str> {
+        [
+            (self.bind_now, "DF_BIND_NOW"),
+            (self.nodeflib, "DF_1_NODEFLIB"),
+            (self.nodelete, "DF_1_NODELETE"),
+            (self.noopen, "DF_1_NOOPEN"),
+            (self.now, "DF_1_NOW"),
+            (self.origin, "DF_ORIGIN"),
+            (self.pie, "DF_1_PIE"),
+            (self.static_tls, "DF_STATIC_TLS"),
+            (self.symbolic, "DF_SYMBOLIC"),
+            (self.textrel, "DF_TEXTREL"),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, name)| *name)
+        .collect()
+    }
+}
+
+/// stack protector symbol and the symbol table it was found in
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct CanarySymbol {
+    /// symbol name, e.g. `__stack_chk_fail`
+    pub name: String,
+    /// `.dynsym` or `.symtab`
+    pub table: String,
+}
+
+/// stack protector symbols, matched regardless of the symbol type as
+/// static binaries may provide them as `STT_GNU_IFUNC`
+const CANARY_SYMBOLS: [&str; 4] = [
+    "__stack_chk_fail",
+    "__stack_chk_fail_local",
+    "__stack_chk_guard",
+    "__intel_security_cookie",
+];
+
+/// Sanitizer runtime a binary was built against, sanitizer builds are
+/// meant for testing and not hardened for production
+#[derive(
+    Clone, Copy, Debug, Deserialize, PartialEq, JsonSchema, Serialize,
+)]
+pub enum Sanitizer {
+    ASan,
+    HWASan,
+    MSan,
+    TSan,
+    UBSan,
+}
+
+impl fmt::Display for Sanitizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// symbol name prefixes of each sanitizer runtime
+const SANITIZER_PREFIXES: [(&str, Sanitizer); 5] = [
+    ("__asan_", Sanitizer::ASan),
+    ("__hwasan_", Sanitizer::HWASan),
+    ("__msan_", Sanitizer::MSan),
+    ("__tsan_", Sanitizer::TSan),
+    ("__ubsan_handle_", Sanitizer::UBSan),
+];
+
+/// Clang CFI runtime entry points, `__cfi_check` is emitted by
+/// `-fsanitize-cfi-cross-dso`
+const CFI_SYMBOLS: [&str; 3] =
+    ["__cfi_check", "__cfi_slowpath", "__cfi_slowpath_diag"];
+/// Clang SafeStack runtime symbols, instrumented code references the
+/// unsafe stack pointer
+const SAFESTACK_SYMBOLS: [&str; 2] =
+    ["__safestack_init", "__safestack_unsafe_stack_ptr"];
+
+/// section holding the selfrando trap information used to reorder
+/// functions at load time
+const SELFRANDO_SECTION: &str = ".txtrp";
+/// symbol name prefixes of the selfrando runtime
+const SELFRANDO_PREFIXES: [&str; 2] = ["_TRaP_", "selfrando_"];
+
+/// `R_PPC_IRELATIVE` and `R_PPC64_IRELATIVE`, missing from goblin
+const R_PPC_IRELATIVE: u32 = 248;
+/// `R_RISCV_IRELATIVE`, missing from goblin
+const R_RISCV_IRELATIVE: u32 = 58;
+/// `R_390_IRELATIVE`, missing from goblin
+const R_390_IRELATIVE: u32 = 61;
+/// `R_SPARC_IRELATIVE`, missing from goblin
+const R_SPARC_IRELATIVE: u32 = 249;
+
+/// GNU indirect functions, their resolvers run during relocation before
+/// `PT_GNU_RELRO` is made read-only and before `main`
+#[derive(Debug, Default, Deserialize, JsonSchema, Serialize)]
+pub struct Ifunc {
+    /// `R_*_IRELATIVE` relocations calling a resolver at load time
+    pub irelative: u32,
+    /// `STT_GNU_IFUNC` symbols, from `.symtab` or `.dynsym` when stripped
+    pub symbols: u32,
+}
+impl Ifunc {
+    /// neither symbols nor relocations
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.irelative == 0 && self.symbols == 0
+    }
+}
+
+/// address range made read-only after relocation by `PT_GNU_RELRO`
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct RelroSegment {
+    /// virtual address of the segment
+    pub vaddr: u64,
+    /// size of the segment in memory
+    pub memsz: u64,
+}
+
+/// `PT_LOAD` program header that is both writable and executable
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct RwxSegment {
+    /// index of the program header
+    pub index: usize,
+    /// virtual address of the segment
+    pub vaddr: u64,
+}
+
+/// Writable and executable program header breaking W^X
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct WxViolation {
+    /// `p_flags` as `R`, `W` and `X` with `-` for unset flags
+    pub flags: String,
+    /// program header type, `PT_LOAD` or `PT_GNU_STACK`
+    pub header: String,
+    /// index of the program header
+    pub index: usize,
+    /// virtual address of the segment
+    pub vaddr: u64,
+}
+
+/// `p_flags` in the `RWX` notation of `readelf -l`
+fn segment_flags(p_flags: u32) -> String {
+    [(PF_R, 'R'), (PF_W, 'W'), (PF_X, 'X')]
+        .iter()
+        .map(|(flag, c)| if p_flags & flag == 0 { '-' } else { *c })
+        .collect()
+}
+
 /// Checksec result struct for ELF32/64 binaries
 ///
 /// **Example**
@@ -95,55 +530,392 @@ impl fmt::Display for PIE {
 ///
 /// pub fn print_results(binary: &String) {
 ///     if let Ok(fp) = fs::File::open(&binary) {
-///         if let Ok(buf) = fs::read(fp) {
+///         if let Ok(buf) = fs::read(&binary) {
 ///             if let Ok(elf) = Elf::parse(&buf) {
-///                 println!("{:#?}", ElfCheckSecResults::parse(&elf));
+///                 println!("{:#?}", CheckSecResults::parse(&elf, &buf));
 ///             }
 ///         }
 ///     }
 /// }
 /// ```
+///
+/// Some of the mitigations/security features that are checked are stored
+/// in ELF notes, so both the goblin object and the raw bytes of the
+/// original file must be provided for evaluating ELF32/64 binaries.
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+#[schemars(rename = "ElfCheckSecResults")]
 pub struct CheckSecResults {
+    /// Machine architecture (`e_machine`), bitness is part of the binary
+    /// type
+    pub arch: String,
+    /// BSD specific mitigation markers, `None` for other operating systems
+    pub bsd: Option<Bsd>,
+    /// AArch64 Branch Target Identification
+    /// (*CFLAGS=*`-mbranch-protection=bti`), `None` for other machines
+    pub bti: Option<bool>,
+    /// `NT_GNU_BUILD_ID` note as a hex string *(not a security mitigation)*
+    pub build_id: Option<String>,
     /// Stack Canary (*CFLAGS=*`-fstack-protector*`)
     pub canary: bool,
+    /// stack protector symbol the canary was detected by
+    pub canary_symbol: Option<CanarySymbol>,
     /// Clang Control Flow Integrity (*CFLAGS=*`-fsanitize=cfi-*`)
     pub clang_cfi: bool,
     /// Clang SafeStack (*CFLAGS=*`-fsanitize=safe-stack`)
     pub clang_safestack: bool,
+    /// file name of the separate debug info from `.gnu_debuglink` *(not a
+    /// security mitigation)*
+    pub debuglink: Option<String>,
+    /// `DT_FLAGS`/`DT_FLAGS_1`, `None` without a `PT_DYNAMIC` program header
+    pub dynamic_flags: Option<DynamicFlags>,
+    /// Number of executable `PT_LOAD` segments
+    pub executable_segments: usize,
     /// Fortify (*CFLAGS=*`-D_FORTIFY_SOURCE`)
     pub fortify: bool,
     /// Fortified functions
     pub fortified: u32,
-    //fortifiable:  Option<Vec<OsString>>,
+    /// Fortifiable functions (fortified and unfortified)
+    pub fortifiable: u32,
+    /// `PT_GNU_STACK` program header present, loaders map an executable
+    /// stack for binaries without one
+    pub gnu_stack: bool,
+    /// Intel CET Indirect Branch Tracking (*CFLAGS=*`-fcf-protection`)
+    pub ibt: bool,
+    /// IFUNC resolvers *(not a security mitigation)*, they explain fuzzy
+    /// canary and fortify results as they pull in unused CPU specific
+    /// implementations
+    pub ifunc: Ifunc,
+    /// `PT_INTERP` program loader path, `None` for static binaries
+    pub interpreter: Option<String>,
+    /// PLT slots are resolved on their first call, which keeps them
+    /// writable for the whole run even with partial RELRO
+    pub lazy_binding: bool,
+    /// Dynamic, static or static-pie linkage
+    pub linkage: Linkage,
     /// No Execute
     pub nx: bool,
+    /// AArch64 Pointer Authentication
+    /// (*CFLAGS=*`-mbranch-protection=pac-ret`), `None` for other machines
+    pub pac: Option<bool>,
     /// Position Inpendent Executable (*CFLAGS=*`-pie -fPIE`)
     pub pie: PIE,
+    /// PLT and GOT sections present, `.plt.got` and `.plt.sec` call
+    /// through GOT entries bound at load time
+    pub plt_sections: Vec<String>,
     /// Relocation Read-Only
     pub relro: Relro,
+    /// `PT_GNU_RELRO` program header
+    pub relro_segment: Option<RelroSegment>,
     /// Run-time search path (`DT_RPATH`)
     pub rpath: VecRpath,
-    /// Run-time search path (`DT_RUNTIME`)
+    /// Run-time search path (`DT_RUNPATH`), takes precedence over
+    /// `DT_RPATH` when both are present
     pub runpath: VecRpath,
+    /// Go or Rust runtime detected from sections and symbols *(not a
+    /// security mitigation)*
+    pub runtime: Runtime,
+    /// Writable and executable `PT_LOAD` segments
+    pub rwx: bool,
+    /// Index and virtual address of the writable and executable segments
+    pub rwx_segments: Vec<RwxSegment>,
+    /// Sanitizer runtimes (*CFLAGS=*`-fsanitize=address,undefined,...`),
+    /// not a security mitigation
+    pub sanitizers: Vec<Sanitizer>,
+    /// entropy of the contents of every section, or of the `PT_LOAD` segments
+    /// without section headers, empty after
+    /// [`crate::binary::Binary::strip_section_entropy`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub section_entropy: Vec<SectionEntropy>,
+    /// selfrando load-time function layout randomization *(heuristic, low
+    /// confidence)*
+    pub selfrando: bool,
+    /// Intel CET Shadow Stack (*CFLAGS=*`-fcf-protection`), only
+    /// advertised compatibility: the linker sets
+    /// `GNU_PROPERTY_X86_FEATURE_1_SHSTK` when every input object is
+    /// marked, but the loader only enables the shadow stack when every
+    /// loaded library is marked as well and the kernel supports it
+    pub shstk: bool,
+    /// Stripped (no `SHT_SYMTAB` section)
+    pub stripped: bool,
+    /// Text Relocations (`DT_TEXTREL`/`DF_TEXTREL`)
+    pub textrel: bool,
+    /// likely misconfigurations that make the binary look more hardened
+    /// than it is, e.g. a PIE with an executable stack
+    pub warnings: Vec<String>,
+    /// W^X: no program header is both writable and executable, the stack
+    /// is not executable and a BSD binary does not opt out of W^X,
+    /// `PT_LOAD` segments covered by `PT_GNU_RELRO` are only writable
+    /// until relocation is done
+    pub wx_compliant: bool,
+    /// Program headers breaking W^X
+    pub wx_violations: Vec<WxViolation>,
 }
 impl CheckSecResults {
     #[must_use]
-    pub fn parse(elf: &Elf) -> Self {
-        Self {
+    pub fn parse(elf: &Elf, bytes: &[u8]) -> Self {
+        let rwx_segments = elf.has_rwx_segments();
+        let mut results = Self {
+            arch: elf.get_arch(),
+            bsd: elf.get_bsd(bytes),
+            bti: elf.has_bti(bytes),
+            build_id: elf.get_build_id(bytes),
             canary: elf.has_canary(),
+            canary_symbol: elf.get_canary_symbol(),
             clang_cfi: elf.has_clang_cfi(),
             clang_safestack: elf.has_clang_safestack(),
+            debuglink: elf.get_debuglink(bytes),
+            dynamic_flags: elf.get_dynamic_flags(),
+            executable_segments: elf.get_executable_segments(),
             fortify: elf.has_fortify(),
             fortified: elf.has_fortified(),
+            fortifiable: elf.has_fortifiable(),
+            gnu_stack: elf.has_gnu_stack(),
+            ibt: elf.has_ibt(bytes),
+            ifunc: elf.get_ifunc(),
+            interpreter: elf.interpreter.map(String::from),
+            lazy_binding: elf.has_lazy_binding(),
+            linkage: elf.get_linkage(),
             nx: elf.has_nx(),
+            pac: elf.has_pac(bytes),
             pie: elf.has_pie(),
+            plt_sections: elf.get_plt_sections(),
             relro: elf.has_relro(),
+            relro_segment: elf.get_relro_segment(),
             rpath: elf.has_rpath(),
             runpath: elf.has_runpath(),
+            runtime: elf.get_runtime(),
+            rwx: !rwx_segments.is_empty(),
+            rwx_segments,
+            sanitizers: elf.has_sanitizers(),
+            section_entropy: elf.get_section_entropy(bytes),
+            selfrando: elf.has_selfrando(),
+            shstk: elf.has_shstk(bytes),
+            stripped: elf.is_stripped(),
+            textrel: elf.has_textrel(),
+            warnings: Vec::new(),
+            wx_compliant: false,
+            wx_violations: elf.has_wx_violations(),
+        };
+        results.wx_compliant = results.nx
+            && results.wx_violations.is_empty()
+            && !results.bsd.as_ref().is_some_and(|bsd| bsd.wxneeded);
+        results.warnings = results.derive_warnings();
+        results
+    }
+    /// advisory checks combining the results of the base checks
+    fn derive_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let position_independent = matches!(self.pie, PIE::PIE | PIE::DSO);
+        if position_independent && !self.nx {
+            warnings.push(
+                "position independent but the stack is executable".to_string(),
+            );
+        }
+        if position_independent && self.textrel {
+            warnings.push(
+                "position independent but code is relocated at load time \
+                 (TEXTREL)"
+                    .to_string(),
+            );
         }
+        if let Some(bsd) = &self.bsd {
+            if bsd.wxneeded {
+                warnings.push(format!(
+                    "{} does not enforce W^X for this binary",
+                    bsd.os
+                ));
+            }
+            if bsd.aslr_disabled {
+                warnings.push(format!(
+                    "{} does not randomize this binary",
+                    bsd.os
+                ));
+            }
+        }
+        warnings
     }
+    /// how the GOT is exposed to overwrites given the lazy binding and
+    /// RELRO results, `None` without `PT_DYNAMIC`
+    fn binding_note(&self) -> Option<String> {
+        self.dynamic_flags.as_ref()?;
+        let exposure = match (self.lazy_binding, &self.relro) {
+            (true, Relro::Partial) => {
+                "PLT slots resolved on first call, .got.plt stays writable \
+                 despite partial RELRO"
+            }
+            (true, _) => {
+                "PLT slots resolved on first call, the GOT is writable for \
+                 the whole run without RELRO"
+            }
+            (false, Relro::Full) => {
+                "bound at load time, the GOT is read-only once relocated \
+                 (full RELRO)"
+            }
+            (false, _) => {
+                "no lazily bound PLT slots, but the GOT stays writable \
+                 without full RELRO"
+            }
+        };
+        Some(if self.plt_sections.is_empty() {
+            exposure.to_string()
+        } else {
+            format!("{} [{}]", exposure, self.plt_sections.join(" "))
+        })
+    }
+    /// supporting detail for verbose output as `(label, evidence)` pairs
+    #[must_use]
+    pub fn evidence(&self) -> Vec<(&'static str, String)> {
+        let mut evidence = Vec::new();
+        if let Some(symbol) = &self.canary_symbol {
+            evidence.push((
+                "Canary",
+                format!("{} in {}", symbol.name, symbol.table),
+            ));
+        } else if self.stripped && self.linkage == Linkage::Static {
+            evidence.push((
+                "Canary",
+                "unknown, no symbols left in the static binary".to_string(),
+            ));
+        }
+        if let Some(flags) = &self.dynamic_flags {
+            let names = flags.names();
+            if !names.is_empty() {
+                evidence.push(("DT_FLAGS", names.join(" ")));
+            }
+        }
+        evidence.push((
+            "NX",
+            if !self.gnu_stack {
+                "no PT_GNU_STACK program header"
+            } else if self.nx {
+                "PT_GNU_STACK without PF_X"
+            } else {
+                "PT_GNU_STACK with PF_X"
+            }
+            .to_string(),
+        ));
+        if self.shstk {
+            evidence.push((
+                "SHSTK",
+                if self.linkage == Linkage::Dynamic {
+                    "advertised compatible, only enforced when every loaded \
+                     library is marked too and the kernel enables CET"
+                } else {
+                    "advertised compatible by the only loaded object, \
+                     enforced when the kernel enables CET"
+                }
+                .to_string(),
+            ));
+        }
+        if let Some(segment) = &self.relro_segment {
+            evidence.push((
+                "Relro",
+                format!(
+                    "PT_GNU_RELRO {:#x}-{:#x}",
+                    segment.vaddr,
+                    segment.vaddr.saturating_add(segment.memsz)
+                ),
+            ));
+        }
+        for segment in &self.rwx_segments {
+            evidence.push((
+                "RWX Segments",
+                format!(
+                    "program header {} at {:#x}",
+                    segment.index, segment.vaddr
+                ),
+            ));
+        }
+        evidence.push((
+            "W^X",
+            format!(
+                "{} executable PT_LOAD segments{}",
+                self.executable_segments,
+                if self.wx_compliant { ", compliant" } else { "" }
+            ),
+        ));
+        for violation in &self.wx_violations {
+            evidence.push((
+                "W^X",
+                format!(
+                    "{} program header {} {} at {:#x}",
+                    violation.header,
+                    violation.index,
+                    violation.flags,
+                    violation.vaddr
+                ),
+            ));
+        }
+        if let Some(binding) = self.binding_note() {
+            evidence.push(("Lazy Binding", binding));
+        }
+        if !self.ifunc.is_empty() {
+            evidence.push((
+                "IFUNC",
+                format!(
+                    "{} STT_GNU_IFUNC symbols, {} IRELATIVE relocations",
+                    self.ifunc.symbols, self.ifunc.irelative
+                ),
+            ));
+        }
+        if let Some(bsd) = &self.bsd {
+            evidence.push((
+                "BSD",
+                if bsd.markers.is_empty() {
+                    format!("{}, no mitigation markers", bsd.os)
+                } else {
+                    format!("{}, {}", bsd.os, bsd.markers.join(" "))
+                },
+            ));
+        }
+        if let Some(build_id) = &self.build_id {
+            evidence.push(("Build ID", build_id.clone()));
+        }
+        if let Some(debuglink) = &self.debuglink {
+            evidence.push(("Debuglink", debuglink.clone()));
+        }
+        evidence.extend(entropy::evidence(&self.section_entropy));
+        evidence
+    }
+}
+
+/// explains NX being disabled for binaries without `PT_GNU_STACK`
+const NO_GNU_STACK_NOTE: &str = "(no PT_GNU_STACK: executable stack)";
+/// explains a missing canary and fortify of Go binaries
+const GO_NOTE: &str = "(Go runtime: C canary/fortify N/A)";
+/// explains a missing canary and fortify of Rust binaries
+const RUST_NOTE: &str = "(Rust runtime: canary/fortify only cover C code)";
+
+/// note on the C centric checks for binaries of other runtimes
+const fn runtime_note(runtime: &Runtime) -> Option<&'static str> {
+    match runtime {
+        Runtime::Go => Some(GO_NOTE),
+        Runtime::Rust => Some(RUST_NOTE),
+        Runtime::Unknown => None,
+    }
+}
+
+/// symbols are bound at load time (`DF_BIND_NOW`, `DF_1_NOW` or
+/// `DT_BIND_NOW`)
+fn binds_now(elf: &Elf) -> bool {
+    match (&elf.dynamic, elf.get_dynamic_flags()) {
+        (Some(dynamic), Some(flags)) => {
+            flags.bind_now
+                || flags.now
+                || dynamic.dyns.iter().any(|dyn_| dyn_.d_tag == DT_BIND_NOW)
+        }
+        _ => false,
+    }
+}
+
+/// comma separated sanitizer names
+fn sanitizer_list(sanitizers: &[Sanitizer]) -> String {
+    sanitizers
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
 }
 
 impl fmt::Display for CheckSecResults {
@@ -152,26 +924,62 @@ impl fmt::Display for CheckSecResults {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Canary: {} CFI: {} SafeStack: {} Fortify: {} Fortified: {:2} \
-            NX: {} PIE: {} Relro: {} RPATH: {} RUNPATH: {}",
+            "BTI: {} Canary: {} CFI: {} SafeStack: {} Fortify: {} \
+            Fortified: {:2} Fortifiable: {:2} IBT: {} Interpreter: {} \
+            Linkage: {} NX: {} PAC: {} PIE: {} Relro: {} RPATH: {} \
+            RUNPATH: {} RWX Segments: {} SHSTK: {} Symbols: {} TEXTREL: {}",
+            option_bool!(self.bti),
             self.canary,
             self.clang_cfi,
             self.clang_safestack,
             self.fortify,
             self.fortified,
+            self.fortifiable,
+            self.ibt,
+            self.interpreter.as_deref().unwrap_or("None"),
+            self.linkage,
             self.nx,
+            option_bool!(self.pac),
             self.pie,
             self.relro,
             self.rpath,
-            self.runpath
-        )
+            self.runpath,
+            self.rwx_segments.len(),
+            self.shstk,
+            !self.stripped,
+            self.textrel
+        )?;
+        if !self.gnu_stack {
+            write!(f, " {}", NO_GNU_STACK_NOTE)?;
+        }
+        if let Some(note) = runtime_note(&self.runtime) {
+            write!(f, " {}", note)?;
+        }
+        if let Some(bsd) =
+            self.bsd.as_ref().filter(|bsd| !bsd.markers.is_empty())
+        {
+            write!(f, " {}: {}", bsd.os, bsd.markers.join(" "))?;
+        }
+        if !self.sanitizers.is_empty() {
+            write!(f, " Sanitizers: {}", sanitizer_list(&self.sanitizers))?;
+        }
+        if self.selfrando {
+            write!(f, " Selfrando: true")?;
+        }
+        if !self.warnings.is_empty() {
+            write!(f, " Warnings: {}", self.warnings.join("; "))?;
+        }
+        Ok(())
     }
     #[cfg(feature = "color")]
     /// Colorized human readable format output
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{} {} {} {} {} {} {} {} {} {:2} {} {} {} {} {} {} {} {} {} {}",
+            "{} {} {} {} {} {} {} {} {} {} {} {:2} {} {:2} {} {} {} {} {} {} \
+             {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}",
+            "BTI:".bold(),
+            colorize_option_bool!(self.bti),
             "Canary:".bold(),
             colorize_bool!(self.canary),
             "CFI:".bold(),
@@ -182,8 +990,18 @@ impl fmt::Display for CheckSecResults {
             colorize_bool!(self.fortify),
             "Fortified:".bold(),
             self.fortified,
+            "Fortifiable:".bold(),
+            self.fortifiable,
+            "IBT:".bold(),
+            colorize_bool!(self.ibt),
+            "Interpreter:".bold(),
+            self.interpreter.as_deref().unwrap_or("None"),
+            "Linkage:".bold(),
+            self.linkage,
             "NX:".bold(),
             colorize_bool!(self.nx),
+            "PAC:".bold(),
+            colorize_option_bool!(self.pac),
             "PIE:".bold(),
             self.pie,
             "Relro:".bold(),
@@ -191,8 +1009,56 @@ impl fmt::Display for CheckSecResults {
             "RPATH:".bold(),
             self.rpath,
             "RUNPATH:".bold(),
-            self.runpath
-        )
+            self.runpath,
+            "RWX Segments:".bold(),
+            if self.rwx {
+                self.rwx_segments.len().to_string().red()
+            } else {
+                self.rwx_segments.len().to_string().green()
+            },
+            "SHSTK:".bold(),
+            colorize_bool!(self.shstk),
+            "Symbols:".bold(),
+            format_args!("{:<5}", !self.stripped),
+            "TEXTREL:".bold(),
+            colorize_bool_inverse!(self.textrel)
+        )?;
+        if !self.gnu_stack {
+            write!(f, " {}", NO_GNU_STACK_NOTE.dimmed())?;
+        }
+        if let Some(note) = runtime_note(&self.runtime) {
+            write!(f, " {}", note.dimmed())?;
+        }
+        if let Some(bsd) =
+            self.bsd.as_ref().filter(|bsd| !bsd.markers.is_empty())
+        {
+            write!(
+                f,
+                " {} {}",
+                format!("{}:", bsd.os).bold(),
+                bsd.markers.join(" ").yellow()
+            )?;
+        }
+        if !self.sanitizers.is_empty() {
+            write!(
+                f,
+                " {} {}",
+                "Sanitizers:".bold(),
+                sanitizer_list(&self.sanitizers).yellow()
+            )?;
+        }
+        if self.selfrando {
+            write!(f, " {} {}", "Selfrando:".bold(), "true".bright_green())?;
+        }
+        if !self.warnings.is_empty() {
+            write!(
+                f,
+                " {} {}",
+                "Warnings:".bold(),
+                self.warnings.join("; ").red()
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -208,7 +1074,7 @@ impl fmt::Display for CheckSecResults {
 ///
 /// pub fn print_results(binary: &String) {
 ///     if let Ok(fp) = fs::File::open(&binary) {
-///         if let Ok(buf) = fs::read(fp) {
+///         if let Ok(buf) = fs::read(&binary) {
 ///             if let Ok(elf) = Elf::parse(&buf) {
 ///                 println!("Canary: {}", elf.has_canary());
 ///             }
@@ -217,60 +1083,180 @@ impl fmt::Display for CheckSecResults {
 /// }
 /// ```
 pub trait Properties {
-    /// check for `__stack_chk_fail` or `__intel_security_cookie` in dynstrtab
+    /// check for `GNU_PROPERTY_AARCH64_FEATURE_1_BTI` in the
+    /// `GNU_PROPERTY_AARCH64_FEATURE_1_AND` property of the
+    /// `.note.gnu.property` ELF note, `None` if the ELF machine is not
+    /// `EM_AARCH64`
+    ///
+    /// requires the raw bytes of the original file to read & parse the
+    /// ELF notes
+    fn has_bti(&self, bytes: &[u8]) -> Option<bool>;
+    /// check for a stack protector symbol, see [`Self::get_canary_symbol`]
     fn has_canary(&self) -> bool;
-    /// check for symbols containing `.cfi` in dynstrtab
+    /// check for symbols containing `.cfi` *(CFI jump table entries)* or
+    /// the `__cfi_check`/`__cfi_slowpath` runtime in syms and dynsyms
     fn has_clang_cfi(&self) -> bool;
-    /// check for `__safestack_init` in dynstrtab
+    /// check for `__safestack_init` or `__safestack_unsafe_stack_ptr` in
+    /// syms and dynsyms
     fn has_clang_safestack(&self) -> bool;
     /// check for symbols ending in `_chk` from dynstrtab
     fn has_fortify(&self) -> bool;
     /// counts symbols ending in `_chk` from dynstrtab
     fn has_fortified(&self) -> u32;
-    // requires running platform to be Linux
-    //fn has_fortifiable(&self) -> u32;
-    /// check `p_flags` of the `PT_GNU_STACK` ELF header
+    /// counts symbols ending in `_chk` along with imported libc functions
+    /// from dynstrtab that have a fortified `__*_chk` variant
+    fn has_fortifiable(&self) -> u32;
+    /// check for the `PT_GNU_STACK` ELF program header
+    fn has_gnu_stack(&self) -> bool;
+    /// check for `GNU_PROPERTY_X86_FEATURE_1_IBT` in the
+    /// `GNU_PROPERTY_X86_FEATURE_1_AND` property of the `.note.gnu.property`
+    /// ELF note
+    ///
+    /// requires the raw bytes of the original file to read & parse the
+    /// ELF notes
+    fn has_ibt(&self, bytes: &[u8]) -> bool;
+    /// check for `DT_JMPREL` relocations without load time binding, the
+    /// loader then resolves PLT slots on their first call
+    fn has_lazy_binding(&self) -> bool;
+    /// check `p_flags` of the `PT_GNU_STACK` ELF program header for the
+    /// absence of `PF_X`, binaries without the header have an executable
+    /// stack
     fn has_nx(&self) -> bool;
-    /// check `d_val` of `DT_FLAGS`/`DT_FLAGS_1` of the `PT_DYN ELF` header
+    /// check for `GNU_PROPERTY_AARCH64_FEATURE_1_PAC` in the
+    /// `GNU_PROPERTY_AARCH64_FEATURE_1_AND` property of the
+    /// `.note.gnu.property` ELF note, `None` if the ELF machine is not
+    /// `EM_AARCH64`
+    ///
+    /// requires the raw bytes of the original file to read & parse the
+    /// ELF notes
+    fn has_pac(&self, bytes: &[u8]) -> Option<bool>;
+    /// check the ELF type, the `PT_INTERP` program header and `DF_1_PIE` in
+    /// `DT_FLAGS_1` of the `PT_DYNAMIC` ELF program header
     fn has_pie(&self) -> PIE;
-    /// check `d_val` is `DF_BIND_NOW` for `DT_FLAGS`/`DT_FLAGS_1` of the
-    /// `PT_GNU_RELRO ELF` program header
+    /// check for the `PT_GNU_RELRO` ELF program header along with
+    /// `DT_BIND_NOW`, `DF_BIND_NOW` in `DT_FLAGS` or `DF_1_NOW` in
+    /// `DT_FLAGS_1` of the `PT_DYNAMIC` ELF program header, static binaries
+    /// without `PT_DYNAMIC` report no RELRO
     fn has_relro(&self) -> Relro;
-    /// check the`.dynamic` section for `DT_RUNPATH` and return results in a
+    /// check the `.dynamic` section for `DT_RPATH` and return results in a
     /// `VecRpath`
     fn has_rpath(&self) -> VecRpath;
-    /// check the `.dynamic` section for `DT_RPATH` and return results in a
+    /// check the `.dynamic` section for `DT_RUNPATH` and return results in a
     /// `VecRpath`
     fn has_runpath(&self) -> VecRpath;
+    /// check `p_flags` of the `PT_LOAD` ELF program headers for both `PF_W`
+    /// and `PF_X` and return the offending segments
+    fn has_rwx_segments(&self) -> Vec<RwxSegment>;
+    /// check dynsyms and syms for sanitizer runtime symbols such as
+    /// `__asan_init`, `__tsan_init` or `__ubsan_handle_*`
+    fn has_sanitizers(&self) -> Vec<Sanitizer>;
+    /// check for a `.txtrp` section or `_TRaP_*`/`selfrando_*` symbols
+    ///
+    /// this is a low confidence heuristic, stripped section headers hide
+    /// the section and other function reordering tools are not detected
+    fn has_selfrando(&self) -> bool;
+    /// check for `GNU_PROPERTY_X86_FEATURE_1_SHSTK` in the
+    /// `GNU_PROPERTY_X86_FEATURE_1_AND` property of the `.note.gnu.property`
+    /// ELF note
+    ///
+    /// the linker keeps a bit of the `AND` property only when all linked
+    /// objects have it set, so it marks the binary as compatible with a
+    /// shadow stack, not that one is in effect at run time
+    ///
+    /// requires the raw bytes of the original file to read & parse the
+    /// ELF notes
+    fn has_shstk(&self, bytes: &[u8]) -> bool;
+    /// check the `.dynamic` section for `DT_TEXTREL` or `DF_TEXTREL` in
+    /// `DT_FLAGS`
+    fn has_textrel(&self) -> bool;
+    /// check `p_flags` of the `PT_LOAD` and `PT_GNU_STACK` ELF program
+    /// headers for both `PF_W` and `PF_X`, leaving out `PT_LOAD` segments
+    /// that are made read-only by `PT_GNU_RELRO` after relocation
+    fn has_wx_violations(&self) -> Vec<WxViolation>;
+    /// check for the absence of a `SHT_SYMTAB` section, binaries with only
+    /// (or without any) `.dynsym` symbols are considered stripped
+    fn is_stripped(&self) -> bool;
+    /// map `e_machine` of the ELF header to a readable architecture name
+    fn get_arch(&self) -> String;
+    /// return the `NT_GNU_BUILD_ID` note as a hex string
+    ///
+    /// requires the raw bytes of the original file to read the ELF notes
+    fn get_build_id(&self, bytes: &[u8]) -> Option<String>;
+    /// read the OpenBSD program header types and the NetBSD `PaX` and
+    /// FreeBSD feature control notes, `None` unless `EI_OSABI` or the
+    /// ident note names a BSD
+    ///
+    /// requires the raw bytes of the original file to read & parse the
+    /// ELF notes
+    fn get_bsd(&self, bytes: &[u8]) -> Option<Bsd>;
+    /// return the first stack protector symbol *(e.g. `__stack_chk_fail`
+    /// or `__stack_chk_guard`)* from dynsyms, falling back to syms
+    ///
+    /// fully static stripped binaries have neither, so their canary can
+    /// not be determined and is reported as missing
+    fn get_canary_symbol(&self) -> Option<CanarySymbol>;
+    /// return the debug info file name from the `.gnu_debuglink` section
+    ///
+    /// requires the raw bytes of the original file to read the section
+    fn get_debuglink(&self, bytes: &[u8]) -> Option<String>;
     /// return the corresponding string from dynstrtab for a given `d_tag`
     fn get_dynstr_by_tag(&self, tag: u64) -> Option<String>;
+    /// decode `DT_FLAGS` and `DT_FLAGS_1` of the `PT_DYNAMIC` ELF program
+    /// header, `None` for binaries without one
+    fn get_dynamic_flags(&self) -> Option<DynamicFlags>;
+    /// count the `PT_LOAD` ELF program headers with `PF_X`
+    fn get_executable_segments(&self) -> usize;
+    /// return the `pr_data` bitmask of a given `pr_type` from the
+    /// `NT_GNU_PROPERTY_TYPE_0` ELF note
+    fn get_gnu_property(&self, bytes: &[u8], pr_type: u32) -> Option<u32>;
+    /// count `STT_GNU_IFUNC` symbols and the `R_*_IRELATIVE` relocations
+    /// of the dynamic section, or of the relocation sections of static
+    /// binaries (`.rela.iplt`)
+    fn get_ifunc(&self) -> Ifunc;
+    /// classify linkage from the ELF type, the `PT_INTERP` program header,
+    /// `DT_NEEDED` entries and `DF_1_PIE` in `DT_FLAGS_1`
+    fn get_linkage(&self) -> Linkage;
+    /// names of the `.plt*` and `.got*` sections in section header order
+    fn get_plt_sections(&self) -> Vec<String>;
+    /// return the address range of the `PT_GNU_RELRO` ELF program header
+    fn get_relro_segment(&self) -> Option<RelroSegment>;
+    /// detect Go by its `.go.buildinfo`, `.gopclntab` or `.note.go.buildid`
+    /// sections or `runtime.` symbols, and Rust by `rust_*` or `__rust_*`
+    /// symbols of the standard library
+    fn get_runtime(&self) -> Runtime;
+    /// compute the entropy of the file contents of every section with
+    /// contents, falling back to the `PT_LOAD` program headers for
+    /// binaries without section headers
+    ///
+    /// requires the raw bytes of the original file to read the section
+    /// contents
+    fn get_section_entropy(&self, bytes: &[u8]) -> Vec<SectionEntropy>;
 }
 
 impl Properties for Elf<'_> {
-    fn has_canary(&self) -> bool {
-        for sym in &self.dynsyms {
-            if let Some(name) = self.dynstrtab.get_at(sym.st_name) {
-                match name {
-                    "__stack_chk_fail" | "__intel_security_cookie" => {
-                        return true
-                    }
-                    _ => continue,
-                }
-            }
+    fn has_bti(&self, bytes: &[u8]) -> Option<bool> {
+        if self.header.e_machine != EM_AARCH64 {
+            return None;
         }
-        false
+        Some(matches!(
+            self.get_gnu_property(bytes, GNU_PROPERTY_AARCH64_FEATURE_1_AND),
+            Some(features) if features & GNU_PROPERTY_AARCH64_FEATURE_1_BTI != 0
+        ))
+    }
+    fn has_canary(&self) -> bool {
+        self.get_canary_symbol().is_some()
     }
     fn has_clang_cfi(&self) -> bool {
         for sym in &self.syms {
             if let Some(name) = self.strtab.get_at(sym.st_name) {
-                if name.contains(".cfi") {
+                if name.contains(".cfi") || CFI_SYMBOLS.contains(&name) {
                     return true;
                 }
             }
         }
         for sym in &self.dynsyms {
             if let Some(name) = self.dynstrtab.get_at(sym.st_name) {
-                if name.contains(".cfi") || name.contains("_cfi") {
+                if name.contains(".cfi") || CFI_SYMBOLS.contains(&name) {
                     return true;
                 }
             }
@@ -278,14 +1264,17 @@ impl Properties for Elf<'_> {
         false
     }
     fn has_clang_safestack(&self) -> bool {
-        for sym in &self.dynsyms {
-            if let Some(name) = self.dynstrtab.get_at(sym.st_name) {
-                if name == "__safestack_init" {
-                    return true;
-                }
-            }
-        }
-        false
+        // the runtime is linked statically, so its symbols are usually
+        // only left in the symbol table
+        self.dynsyms
+            .iter()
+            .filter_map(|sym| self.dynstrtab.get_at(sym.st_name))
+            .chain(
+                self.syms
+                    .iter()
+                    .filter_map(|sym| self.strtab.get_at(sym.st_name)),
+            )
+            .any(|name| SAFESTACK_SYMBOLS.contains(&name))
     }
     fn has_fortify(&self) -> bool {
         for sym in &self.dynsyms {
@@ -308,11 +1297,36 @@ impl Properties for Elf<'_> {
         }
         fortified_count
     }
-    /*
-    // requires running platform to be Linux
-    fn has_forifiable(&self) -> Option<Vec<OsString>> {
+    fn has_fortifiable(&self) -> u32 {
+        let mut fortifiable_count: u32 = 0;
+        for sym in &self.dynsyms {
+            if let Some(name) = self.dynstrtab.get_at(sym.st_name) {
+                if name.ends_with("_chk")
+                    || (sym.is_import()
+                        && FORTIFIABLE_FUNCTIONS.contains(&name))
+                {
+                    fortifiable_count += 1;
+                }
+            }
+        }
+        fortifiable_count
+    }
+    fn has_gnu_stack(&self) -> bool {
+        self.program_headers.iter().any(|header| header.p_type == PT_GNU_STACK)
+    }
+    fn has_ibt(&self, bytes: &[u8]) -> bool {
+        if let Some(features) =
+            self.get_gnu_property(bytes, GNU_PROPERTY_X86_FEATURE_1_AND)
+        {
+            return features & GNU_PROPERTY_X86_FEATURE_1_IBT != 0;
+        }
+        false
+    }
+    fn has_lazy_binding(&self) -> bool {
+        self.dynamic.is_some()
+            && !self.pltrelocs.is_empty()
+            && !binds_now(self)
     }
-    */
     fn has_nx(&self) -> bool {
         for header in &self.program_headers {
             if header.p_type == PT_GNU_STACK {
@@ -324,40 +1338,48 @@ impl Properties for Elf<'_> {
         }
         false
     }
+    fn has_pac(&self, bytes: &[u8]) -> Option<bool> {
+        if self.header.e_machine != EM_AARCH64 {
+            return None;
+        }
+        Some(matches!(
+            self.get_gnu_property(bytes, GNU_PROPERTY_AARCH64_FEATURE_1_AND),
+            Some(features) if features & GNU_PROPERTY_AARCH64_FEATURE_1_PAC != 0
+        ))
+    }
     fn has_pie(&self) -> PIE {
         if self.header.e_type == ET_DYN {
-            if let Some(dynamic) = &self.dynamic {
-                if DF_1_PIE & dynamic.info.flags_1 == DF_1_PIE {
-                    return PIE::PIE;
-                }
+            // shared libraries are ET_DYN too, but are not started through
+            // the dynamic loader
+            if self.interpreter.is_some() {
+                return PIE::PIE;
+            }
+            // static-pie executables have no interpreter
+            if self.get_dynamic_flags().is_some_and(|flags| flags.pie) {
+                return PIE::PIE;
             }
             return PIE::DSO;
         }
         PIE::None
     }
     fn has_relro(&self) -> Relro {
-        for header in &self.program_headers {
-            if header.p_type == PT_GNU_RELRO {
-                if let Some(dynamic) = &self.dynamic {
-                    if DF_BIND_NOW & dynamic.info.flags == DF_BIND_NOW
-                        && DF_1_NOW & dynamic.info.flags_1 == DF_1_NOW
-                    {
-                        return Relro::Full;
-                    }
-                }
-                return Relro::Partial;
-            }
+        if self.get_relro_segment().is_none() {
+            return Relro::None;
+        }
+        // statically linked binaries have no `PT_DYNAMIC` to bind against
+        if self.dynamic.is_none() {
+            return Relro::None;
+        }
+        if binds_now(self) {
+            Relro::Full
+        } else {
+            Relro::Partial
         }
-        Relro::None
     }
     fn has_rpath(&self) -> VecRpath {
         if self.dynamic.is_some() {
             if let Some(name) = self.get_dynstr_by_tag(DT_RPATH) {
-                return VecRpath::new(
-                    name.split(':')
-                        .map(|path| Rpath::Yes(path.to_string()))
-                        .collect(),
-                );
+                return VecRpath::from_search_path(&name);
             }
         }
         VecRpath::new(vec![Rpath::None])
@@ -365,15 +1387,250 @@ impl Properties for Elf<'_> {
     fn has_runpath(&self) -> VecRpath {
         if self.dynamic.is_some() {
             if let Some(name) = self.get_dynstr_by_tag(DT_RUNPATH) {
-                return VecRpath::new(
-                    name.split(':')
-                        .map(|path| Rpath::Yes(path.to_string()))
-                        .collect(),
-                );
+                return VecRpath::from_search_path(&name);
             }
         }
         VecRpath::new(vec![Rpath::None])
     }
+    fn has_rwx_segments(&self) -> Vec<RwxSegment> {
+        self.program_headers
+            .iter()
+            .enumerate()
+            .filter(|(_, header)| {
+                header.p_type == PT_LOAD
+                    && header.p_flags & (PF_W | PF_X) == PF_W | PF_X
+            })
+            .map(|(index, header)| RwxSegment { index, vaddr: header.p_vaddr })
+            .collect()
+    }
+    fn has_sanitizers(&self) -> Vec<Sanitizer> {
+        let names: Vec<&str> = self
+            .dynsyms
+            .iter()
+            .filter_map(|sym| self.dynstrtab.get_at(sym.st_name))
+            .chain(
+                self.syms
+                    .iter()
+                    .filter_map(|sym| self.strtab.get_at(sym.st_name)),
+            )
+            .collect();
+        SANITIZER_PREFIXES
+            .iter()
+            .filter(|(prefix, _)| {
+                names.iter().any(|name| name.starts_with(prefix))
+            })
+            .map(|(_, sanitizer)| *sanitizer)
+            .collect()
+    }
+    fn has_selfrando(&self) -> bool {
+        self.section_headers.iter().any(|section| {
+            self.shdr_strtab.get_at(section.sh_name) == Some(SELFRANDO_SECTION)
+        }) || self
+            .dynsyms
+            .iter()
+            .filter_map(|sym| self.dynstrtab.get_at(sym.st_name))
+            .chain(
+                self.syms
+                    .iter()
+                    .filter_map(|sym| self.strtab.get_at(sym.st_name)),
+            )
+            .any(|name| {
+                SELFRANDO_PREFIXES
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix))
+            })
+    }
+    fn has_shstk(&self, bytes: &[u8]) -> bool {
+        if let Some(features) =
+            self.get_gnu_property(bytes, GNU_PROPERTY_X86_FEATURE_1_AND)
+        {
+            return features & GNU_PROPERTY_X86_FEATURE_1_SHSTK != 0;
+        }
+        false
+    }
+    fn has_textrel(&self) -> bool {
+        if let (Some(dynamic), Some(flags)) =
+            (&self.dynamic, self.get_dynamic_flags())
+        {
+            return flags.textrel
+                || dynamic.dyns.iter().any(|dyn_| dyn_.d_tag == DT_TEXTREL);
+        }
+        false
+    }
+    fn has_wx_violations(&self) -> Vec<WxViolation> {
+        let relro = self.get_relro_segment();
+        self.program_headers
+            .iter()
+            .enumerate()
+            .filter(|(_, header)| {
+                header.p_flags & (PF_W | PF_X) == PF_W | PF_X
+                    && match header.p_type {
+                        PT_GNU_STACK => true,
+                        PT_LOAD => !relro.as_ref().is_some_and(|relro| {
+                            relro.vaddr <= header.p_vaddr
+                                && header
+                                    .p_vaddr
+                                    .saturating_add(header.p_memsz)
+                                    <= relro.vaddr.saturating_add(relro.memsz)
+                        }),
+                        _ => false,
+                    }
+            })
+            .map(|(index, header)| WxViolation {
+                flags: segment_flags(header.p_flags),
+                header: if header.p_type == PT_LOAD {
+                    "PT_LOAD"
+                } else {
+                    "PT_GNU_STACK"
+                }
+                .to_string(),
+                index,
+                vaddr: header.p_vaddr,
+            })
+            .collect()
+    }
+    fn is_stripped(&self) -> bool {
+        !self
+            .section_headers
+            .iter()
+            .any(|section| section.sh_type == SHT_SYMTAB)
+    }
+    fn get_arch(&self) -> String {
+        match self.header.e_machine {
+            EM_386 => "i386",
+            EM_X86_64 => "x86_64",
+            EM_ARM => "arm",
+            EM_AARCH64 => "aarch64",
+            EM_MIPS if self.is_64 => "mips64",
+            EM_MIPS => "mips",
+            EM_PPC => "powerpc",
+            EM_PPC64 => "powerpc64",
+            EM_RISCV if self.is_64 => "riscv64",
+            EM_RISCV => "riscv32",
+            EM_S390 if self.is_64 => "s390x",
+            EM_S390 => "s390",
+            EM_SPARC => "sparc",
+            EM_SPARCV9 => "sparc64",
+            machine => return machine_to_str(machine).to_lowercase(),
+        }
+        .to_string()
+    }
+    fn get_bsd(&self, bytes: &[u8]) -> Option<Bsd> {
+        let notes: Vec<Note> = self
+            .iter_note_headers(bytes)
+            .or_else(|| self.iter_note_sections(bytes, None))
+            .map(|notes| notes.flatten().collect())
+            .unwrap_or_default();
+        let os = match self.header.e_ident[EI_OSABI] {
+            ELFOSABI_FREEBSD => BsdOs::FreeBSD,
+            ELFOSABI_NETBSD => BsdOs::NetBSD,
+            ELFOSABI_OPENBSD => BsdOs::OpenBSD,
+            // OpenBSD and NetBSD binaries are usually System V and only
+            // carry an ident note
+            ELFOSABI_NONE => notes
+                .iter()
+                .filter(|note| note.n_type == NT_BSD_IDENT)
+                .find_map(|note| match note.name {
+                    "FreeBSD" => Some(BsdOs::FreeBSD),
+                    "NetBSD" => Some(BsdOs::NetBSD),
+                    "OpenBSD" => Some(BsdOs::OpenBSD),
+                    _ => None,
+                })?,
+            _ => return None,
+        };
+        let endian = if self.little_endian {
+            scroll::Endian::Little
+        } else {
+            scroll::Endian::Big
+        };
+        let note_flags = |name: &str, n_type: u32| {
+            notes
+                .iter()
+                .find(|note| note.name == name && note.n_type == n_type)
+                .and_then(|note| note.desc.pread_with::<u32>(0, endian).ok())
+        };
+        let mut bsd = Bsd {
+            aslr_disabled: false,
+            markers: Vec::new(),
+            os,
+            wxneeded: false,
+        };
+        match bsd.os {
+            BsdOs::FreeBSD => {
+                if let Some(flags) =
+                    note_flags("FreeBSD", NT_FREEBSD_FEATURE_CTL)
+                {
+                    bsd.aslr_disabled =
+                        flags & NT_FREEBSD_FCTL_ASLR_DISABLE != 0;
+                    bsd.wxneeded = flags & NT_FREEBSD_FCTL_WXNEEDED != 0;
+                    bsd.markers = flag_names(flags, &FREEBSD_FCTL_FLAGS);
+                }
+            }
+            BsdOs::NetBSD => {
+                if let Some(flags) = note_flags("PaX", NT_NETBSD_PAX) {
+                    bsd.aslr_disabled = flags & NT_NETBSD_PAX_NOASLR != 0;
+                    bsd.wxneeded = flags & NT_NETBSD_PAX_NOMPROTECT != 0;
+                    bsd.markers = flag_names(flags, &NETBSD_PAX_FLAGS);
+                }
+            }
+            BsdOs::OpenBSD => {
+                bsd.wxneeded = self
+                    .program_headers
+                    .iter()
+                    .any(|header| header.p_type == PT_OPENBSD_WXNEEDED);
+                bsd.markers = OPENBSD_PROGRAM_HEADERS
+                    .iter()
+                    .filter(|(p_type, _)| {
+                        self.program_headers
+                            .iter()
+                            .any(|header| header.p_type == *p_type)
+                    })
+                    .map(|(_, name)| (*name).to_string())
+                    .collect();
+            }
+        }
+        Some(bsd)
+    }
+    fn get_build_id(&self, bytes: &[u8]) -> Option<String> {
+        // fall back to the `PT_NOTE` program headers for stripped binaries
+        let notes = self
+            .iter_note_sections(bytes, Some(".note.gnu.build-id"))
+            .or_else(|| self.iter_note_headers(bytes))?;
+        notes
+            .flatten()
+            .find(|note| note.n_type == NT_GNU_BUILD_ID && note.name == "GNU")
+            .map(|note| {
+                note.desc.iter().map(|byte| format!("{:02x}", byte)).collect()
+            })
+    }
+    fn get_canary_symbol(&self) -> Option<CanarySymbol> {
+        [
+            (&self.dynsyms, &self.dynstrtab, ".dynsym"),
+            (&self.syms, &self.strtab, ".symtab"),
+        ]
+        .iter()
+        .find_map(|(syms, strtab, table)| {
+            syms.iter()
+                .filter_map(|sym| strtab.get_at(sym.st_name))
+                .find(|name| CANARY_SYMBOLS.contains(name))
+                .map(|name| CanarySymbol {
+                    name: name.to_string(),
+                    table: (*table).to_string(),
+                })
+        })
+    }
+    fn get_debuglink(&self, bytes: &[u8]) -> Option<String> {
+        let section = self.section_headers.iter().find(|section| {
+            self.shdr_strtab.get_at(section.sh_name) == Some(".gnu_debuglink")
+        })?;
+        let data = bytes.get(section.file_range()?)?;
+        // the nul terminated file name is followed by a crc32 of the file
+        let name = data.split(|byte| *byte == 0).next()?;
+        if name.is_empty() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(name).into_owned())
+    }
     fn get_dynstr_by_tag(&self, tag: u64) -> Option<String> {
         if let Some(dynamic) = &self.dynamic {
             for dynamic in &dynamic.dyns {
@@ -389,4 +1646,283 @@ impl Properties for Elf<'_> {
         }
         None
     }
+    fn get_dynamic_flags(&self) -> Option<DynamicFlags> {
+        let info = &self.dynamic.as_ref()?.info;
+        let (flags, flags_1) = (info.flags, info.flags_1);
+        Some(DynamicFlags {
+            bind_now: flags & DF_BIND_NOW != 0,
+            nodeflib: flags_1 & DF_1_NODEFLIB != 0,
+            nodelete: flags_1 & DF_1_NODELETE != 0,
+            noopen: flags_1 & DF_1_NOOPEN != 0,
+            now: flags_1 & DF_1_NOW != 0,
+            origin: flags & DF_ORIGIN != 0 || flags_1 & DF_1_ORIGIN != 0,
+            pie: flags_1 & DF_1_PIE != 0,
+            static_tls: flags & DF_STATIC_TLS != 0,
+            symbolic: flags & DF_SYMBOLIC != 0,
+            textrel: flags & DF_TEXTREL != 0,
+        })
+    }
+    fn get_executable_segments(&self) -> usize {
+        self.program_headers
+            .iter()
+            .filter(|header| {
+                header.p_type == PT_LOAD && header.p_flags & PF_X != 0
+            })
+            .count()
+    }
+    fn get_gnu_property(&self, bytes: &[u8], pr_type: u32) -> Option<u32> {
+        // fall back to the `PT_NOTE` program headers for stripped binaries
+        let notes = self
+            .iter_note_sections(bytes, Some(".note.gnu.property"))
+            .or_else(|| self.iter_note_headers(bytes))?;
+        let endian = if self.little_endian {
+            scroll::Endian::Little
+        } else {
+            scroll::Endian::Big
+        };
+        // properties are padded to the native word size
+        let align = if self.is_64 { 8 } else { 4 };
+        for note in notes.flatten() {
+            if note.n_type != NT_GNU_PROPERTY_TYPE_0 || note.name != "GNU" {
+                continue;
+            }
+            let desc = note.desc;
+            let mut offset: usize = 0;
+            while offset + 8 <= desc.len() {
+                let kind = desc.pread_with::<u32>(offset, endian).ok()?;
+                let datasz =
+                    desc.pread_with::<u32>(offset + 4, endian).ok()? as usize;
+                let data = offset + 8;
+                if kind == pr_type && datasz >= 4 {
+                    return desc.pread_with::<u32>(data, endian).ok();
+                }
+                offset = data.checked_add(datasz)?;
+                offset =
+                    offset.checked_add((align - offset % align) % align)?;
+            }
+        }
+        None
+    }
+    fn get_ifunc(&self) -> Ifunc {
+        let syms =
+            if self.syms.is_empty() { &self.dynsyms } else { &self.syms };
+        let symbols =
+            syms.iter().filter(|sym| sym.st_type() == STT_GNU_IFUNC).count();
+        let irelative = match self.header.e_machine {
+            EM_386 => Some(R_386_IRELATIVE),
+            EM_X86_64 => Some(R_X86_64_IRELATIVE),
+            EM_ARM => Some(R_ARM_IRELATIVE),
+            EM_AARCH64 => Some(R_AARCH64_IRELATIVE),
+            EM_PPC | EM_PPC64 => Some(R_PPC_IRELATIVE),
+            EM_RISCV => Some(R_RISCV_IRELATIVE),
+            EM_S390 => Some(R_390_IRELATIVE),
+            EM_SPARC | EM_SPARCV9 => Some(R_SPARC_IRELATIVE),
+            _ => None,
+        };
+        let irelative = irelative.map_or(0, |r_type| {
+            // the relocation sections repeat the dynamic relocations
+            if self.dynamic.is_some() {
+                [&self.dynrelas, &self.dynrels, &self.pltrelocs]
+                    .iter()
+                    .flat_map(|relocs| relocs.iter())
+                    .filter(|reloc| reloc.r_type == r_type)
+                    .count()
+            } else {
+                self.shdr_relocs
+                    .iter()
+                    .flat_map(|(_, relocs)| relocs.iter())
+                    .filter(|reloc| reloc.r_type == r_type)
+                    .count()
+            }
+        });
+        Ifunc {
+            irelative: u32::try_from(irelative).unwrap_or(u32::MAX),
+            symbols: u32::try_from(symbols).unwrap_or(u32::MAX),
+        }
+    }
+    fn get_linkage(&self) -> Linkage {
+        if self.interpreter.is_some() {
+            return Linkage::Dynamic;
+        }
+        if self.header.e_type == ET_DYN {
+            // shared libraries have no interpreter either and may not
+            // depend on other libraries, static-pie executables are marked
+            // by the linker or carry the self-relocation code of the libc
+            let pie = self
+                .dynamic
+                .as_ref()
+                .is_some_and(|dynamic| dynamic.info.flags_1 & DF_1_PIE != 0);
+            let relocates_itself = self.header.e_entry != 0
+                && self.syms.iter().any(|sym| {
+                    self.strtab.get_at(sym.st_name)
+                        == Some("_dl_relocate_static_pie")
+                });
+            if self.libraries.is_empty() && (pie || relocates_itself) {
+                return Linkage::StaticPie;
+            }
+            return Linkage::Dynamic;
+        }
+        if self.dynamic.is_some() {
+            return Linkage::Dynamic;
+        }
+        Linkage::Static
+    }
+    fn get_plt_sections(&self) -> Vec<String> {
+        self.section_headers
+            .iter()
+            .filter_map(|section| self.shdr_strtab.get_at(section.sh_name))
+            .filter(|name| {
+                name.starts_with(".plt") || name.starts_with(".got")
+            })
+            .map(String::from)
+            .collect()
+    }
+    fn get_relro_segment(&self) -> Option<RelroSegment> {
+        self.program_headers
+            .iter()
+            .find(|header| header.p_type == PT_GNU_RELRO)
+            .map(|header| RelroSegment {
+                vaddr: header.p_vaddr,
+                memsz: header.p_memsz,
+            })
+    }
+    fn get_runtime(&self) -> Runtime {
+        let go_section = self.section_headers.iter().any(|section| {
+            matches!(
+                self.shdr_strtab.get_at(section.sh_name),
+                Some(".go.buildinfo" | ".gopclntab" | ".note.go.buildid")
+            )
+        });
+        let names: Vec<&str> = self
+            .syms
+            .iter()
+            .filter_map(|sym| self.strtab.get_at(sym.st_name))
+            .chain(
+                self.dynsyms
+                    .iter()
+                    .filter_map(|sym| self.dynstrtab.get_at(sym.st_name)),
+            )
+            .collect();
+        if go_section || names.iter().any(|name| name.starts_with("runtime."))
+        {
+            Runtime::Go
+        } else if names.iter().any(|name| {
+            name.starts_with("rust_") || name.starts_with("__rust_")
+        }) {
+            Runtime::Rust
+        } else {
+            Runtime::Unknown
+        }
+    }
+    fn get_section_entropy(&self, bytes: &[u8]) -> Vec<SectionEntropy> {
+        if self.section_headers.is_empty() {
+            return self
+                .program_headers
+                .iter()
+                .enumerate()
+                .filter(|(_, header)| header.p_type == PT_LOAD)
+                .filter_map(|(index, header)| {
+                    let data = bytes.get(header.file_range())?;
+                    Some(SectionEntropy::new(
+                        format!("segment {}", index),
+                        data,
+                    ))
+                })
+                .collect();
+        }
+        self.section_headers
+            .iter()
+            .filter_map(|section| {
+                let data = bytes.get(section.file_range()?)?;
+                if data.is_empty() {
+                    return None;
+                }
+                let name = self.shdr_strtab.get_at(section.sh_name)?;
+                Some(SectionEntropy::new(name.to_string(), data))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// checks of `true-x86_64` after `patch` rewrote the `p_type` and
+    /// `p_flags` of its `PT_GNU_STACK` program header
+    fn patched_gnu_stack(patch: fn(&mut u32, &mut u32)) -> CheckSecResults {
+        let mut bytes = fs::read("test/binaries/true-x86_64").unwrap();
+        let elf = Elf::parse(&bytes).unwrap();
+        let idx = elf
+            .program_headers
+            .iter()
+            .position(|header| header.p_type == PT_GNU_STACK)
+            .unwrap();
+        let offset = elf.header.e_phoff as usize
+            + idx * elf.header.e_phentsize as usize;
+        let word = |bytes: &[u8], at: usize| {
+            bytes.pread_with::<u32>(at, scroll::LE).unwrap()
+        };
+        let (mut p_type, mut p_flags) =
+            (word(&bytes, offset), word(&bytes, offset + 4));
+        patch(&mut p_type, &mut p_flags);
+        bytes[offset..offset + 4].copy_from_slice(&p_type.to_le_bytes());
+        bytes[offset + 4..offset + 8].copy_from_slice(&p_flags.to_le_bytes());
+        CheckSecResults::parse(&Elf::parse(&bytes).unwrap(), &bytes)
+    }
+
+    #[test]
+    fn nx_gnu_stack() {
+        let results = patched_gnu_stack(|_, _| ());
+        assert!(results.gnu_stack);
+        assert!(results.nx);
+    }
+
+    #[test]
+    fn nx_execstack() {
+        // `-z execstack`
+        let results = patched_gnu_stack(|_, p_flags| *p_flags |= PF_X);
+        assert!(results.gnu_stack);
+        assert!(!results.nx);
+    }
+
+    #[test]
+    fn nx_without_gnu_stack() {
+        let results = patched_gnu_stack(|p_type, _| *p_type = 0);
+        assert!(!results.gnu_stack);
+        assert!(!results.nx);
+        assert!(results.to_string().contains(NO_GNU_STACK_NOTE));
+    }
+    /// checks of `true-x86_64` with the dynamic symbol `from` renamed to
+    /// `to`, which may not be longer
+    fn renamed_dynsym(from: &str, to: &str) -> CheckSecResults {
+        let mut bytes = fs::read("test/binaries/true-x86_64").unwrap();
+        let name = format!("\0{}\0", from);
+        let offset = bytes
+            .windows(name.len())
+            .position(|window| window == name.as_bytes())
+            .unwrap();
+        let renamed = format!("{}\0", to);
+        bytes[offset + 1..][..renamed.len()]
+            .copy_from_slice(renamed.as_bytes());
+        CheckSecResults::parse(&Elf::parse(&bytes).unwrap(), &bytes)
+    }
+
+    #[test]
+    fn clang_cfi_symbols() {
+        // libdw imports are not CFI runtime symbols
+        assert!(!renamed_dynsym("bindtextdomain", "dwarf_cfi_x").clang_cfi);
+        let results = renamed_dynsym("bindtextdomain", "__cfi_slowpath");
+        assert!(results.clang_cfi);
+        assert!(!results.clang_safestack);
+    }
+
+    #[test]
+    fn clang_safestack_symbols() {
+        let results =
+            renamed_dynsym("__ctype_get_mb_cur_max", "__safestack_init");
+        assert!(results.clang_safestack);
+        assert!(!results.clang_cfi);
+    }
 }
@@ -0,0 +1,109 @@
+//! Implements the section entropy heuristic shared by all formats
+//!
+//! Compressed or encrypted data is close to the maximum of 8 bits of
+//! entropy per byte, while code and data of regular binaries stay well
+//! below. Sections above [`PACKED_ENTROPY`] are a strong hint of a packer
+//! or an encrypted payload, not proof of one.
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// entropy in bits per byte above which a section is likely packed or
+/// encrypted
+pub const PACKED_ENTROPY: f64 = 7.2;
+/// smaller sections can not reach a meaningful entropy and are never
+/// flagged as packed
+pub const MIN_PACKED_SIZE: usize = 512;
+
+/// Shannon entropy of `bytes` in bits per byte, from `0.0` to `8.0`
+///
+/// the bytes are only counted in a single pass, so large sections do not
+/// need any additional memory
+#[must_use]
+pub fn shannon(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0_u64; 256];
+    for byte in bytes {
+        counts[*byte as usize] += 1;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let len = bytes.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            #[allow(clippy::cast_precision_loss)]
+            let probability = *count as f64 / len;
+            // `-p * log2(p)` is `-0.0` for a single byte value
+            probability * (1.0 / probability).log2()
+        })
+        .sum()
+}
+
+/// Entropy of the file contents of a single section or segment
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct SectionEntropy {
+    /// Shannon entropy in bits per byte, rounded to two decimals
+    pub entropy: f64,
+    /// section name, `segment N` for program headers of ELF binaries
+    /// without section headers
+    pub name: String,
+    /// number of bytes in the file
+    pub size: u64,
+}
+impl SectionEntropy {
+    #[must_use]
+    pub fn new(name: String, bytes: &[u8]) -> Self {
+        Self {
+            entropy: (shannon(bytes) * 100.0).round() / 100.0,
+            name,
+            size: bytes.len() as u64,
+        }
+    }
+    /// large enough and above [`PACKED_ENTROPY`]
+    #[must_use]
+    pub fn is_packed(&self) -> bool {
+        self.size >= MIN_PACKED_SIZE as u64 && self.entropy > PACKED_ENTROPY
+    }
+}
+
+/// any of the `sections` is likely packed, see
+/// [`SectionEntropy::is_packed`]
+#[must_use]
+pub fn is_packed(sections: &[SectionEntropy]) -> bool {
+    sections.iter().any(SectionEntropy::is_packed)
+}
+
+/// verbose output evidence of every section, `(label, evidence)` pairs
+#[must_use]
+pub fn evidence(sections: &[SectionEntropy]) -> Vec<(&'static str, String)> {
+    sections
+        .iter()
+        .map(|section| {
+            (
+                "Entropy",
+                format!(
+                    "{} {:.2} ({:#x} bytes){}",
+                    section.name,
+                    section.entropy,
+                    section.size,
+                    if section.is_packed() { " packed" } else { "" }
+                ),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shannon_bounds() {
+        assert_eq!(shannon(&[]).to_bits(), 0.0_f64.to_bits());
+        assert_eq!(shannon(&[0x90; 64]).to_bits(), 0.0_f64.to_bits());
+        let uniform: Vec<u8> = (0..=255).collect();
+        assert!((shannon(&uniform) - 8.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,167 @@
+#[cfg(feature = "color")]
+use colored::Colorize;
+use serde::Serialize;
+
+use checksec::binary::{BinSpecificProperties, BinType, Binary};
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// every slice of a fat `MachO` binary in a single record
+#[derive(Serialize)]
+pub struct Fat<'a> {
+    /// results of every slice keyed by its architecture
+    pub architectures: BTreeMap<&'a str, &'a BinSpecificProperties>,
+    pub binarytype: &'a BinType,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    pub duplicates: &'a [String],
+    pub file: &'a str,
+    /// a section of any slice is likely packed or encrypted
+    pub packed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<&'a str>,
+    /// the slices in the order of the fat header
+    #[serde(skip)]
+    slices: Vec<&'a Binary>,
+}
+impl Fat<'_> {
+    fn architectures(&self) -> Vec<&str> {
+        self.slices.iter().filter_map(|bin| bin.arch.as_deref()).collect()
+    }
+}
+#[cfg(not(feature = "color"))]
+impl fmt::Display for Fat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: | Fat: {} slices ({})",
+            self.binarytype,
+            self.slices.len(),
+            self.architectures().join(", ")
+        )?;
+        for (arch, bin) in self.architectures().iter().zip(&self.slices) {
+            write!(f, " | {}: {}", arch, bin.properties)?;
+        }
+        write!(f, " | File: {}", self.file)?;
+        if !self.duplicates.is_empty() {
+            write!(f, " | Duplicates: {}", self.duplicates.join(", "))?;
+        }
+        if self.packed {
+            write!(f, " | Packed: true")?;
+        }
+        // `{:#}` adds the evidence behind the results of every slice
+        if f.alternate() {
+            for (arch, bin) in self.architectures().iter().zip(&self.slices) {
+                for (label, evidence) in bin.properties.evidence() {
+                    write!(f, "\n    {} {}: {}", arch, label, evidence)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+#[cfg(feature = "color")]
+impl fmt::Display for Fat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: | {} {} slices ({})",
+            self.binarytype,
+            "Fat:".bold(),
+            self.slices.len(),
+            self.architectures().join(", ")
+        )?;
+        for (arch, bin) in self.architectures().iter().zip(&self.slices) {
+            write!(
+                f,
+                " | {} {}",
+                format!("{}:", arch).bold(),
+                bin.properties
+            )?;
+        }
+        write!(
+            f,
+            " | {} {}",
+            "File:".bold().underline(),
+            self.file.bright_blue()
+        )?;
+        if !self.duplicates.is_empty() {
+            write!(
+                f,
+                " | {} {}",
+                "Duplicates:".bold(),
+                self.duplicates.join(", ").bright_blue()
+            )?;
+        }
+        if self.packed {
+            write!(f, " | {} {}", "Packed:".bold(), "true".red())?;
+        }
+        // `{:#}` adds the evidence behind the results of every slice
+        if f.alternate() {
+            for (arch, bin) in self.architectures().iter().zip(&self.slices) {
+                for (label, evidence) in bin.properties.evidence() {
+                    write!(
+                        f,
+                        "\n    {} {}",
+                        format!("{} {}:", arch, label).bold(),
+                        evidence
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// a single binary or all slices of a fat `MachO` binary
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Entry<'a> {
+    Binary(&'a Binary),
+    Fat(Fat<'a>),
+}
+impl fmt::Display for Entry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self, f.alternate()) {
+            (Self::Binary(bin), false) => write!(f, "{}", bin),
+            (Self::Binary(bin), true) => write!(f, "{:#}", bin),
+            (Self::Fat(fat), false) => write!(f, "{}", fat),
+            (Self::Fat(fat), true) => write!(f, "{:#}", fat),
+        }
+    }
+}
+
+/// merge the consecutive slices of every fat `MachO` binary in `bins` into
+/// one [`Fat`] entry, other binaries are kept as they are
+pub fn collapse(bins: &[Binary]) -> Vec<Entry<'_>> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut idx = 0;
+    while idx < bins.len() {
+        let first = &bins[idx];
+        if first.arch.is_none() {
+            entries.push(Entry::Binary(first));
+            idx += 1;
+            continue;
+        }
+        let slices: Vec<&Binary> = bins[idx..]
+            .iter()
+            .take_while(|bin| bin.arch.is_some() && bin.file == first.file)
+            .collect();
+        idx += slices.len();
+        entries.push(Entry::Fat(Fat {
+            architectures: slices
+                .iter()
+                .filter_map(|bin| {
+                    bin.arch.as_deref().map(|arch| (arch, &bin.properties))
+                })
+                .collect(),
+            binarytype: &first.binarytype,
+            duplicates: &first.duplicates,
+            file: &first.file,
+            packed: slices.iter().any(|bin| bin.packed),
+            sha256: first.sha256.as_deref(),
+            slices,
+        }));
+    }
+    entries
+}
@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// goblin release pinned in `Cargo.toml`, update together with the
+/// dependency
+const GOBLIN_VERSION: &str = "0.4.3";
+
+/// cargo features and parser version this binary was built with
+#[derive(Serialize)]
+pub struct Features {
+    /// every optional cargo feature and whether it is compiled in
+    pub features: BTreeMap<&'static str, bool>,
+    pub goblin: &'static str,
+    pub version: &'static str,
+}
+impl Features {
+    pub fn new() -> Self {
+        Self {
+            features: [
+                ("color", cfg!(feature = "color")),
+                ("elf", cfg!(feature = "elf")),
+                ("entitlements", cfg!(feature = "entitlements")),
+                ("macho", cfg!(feature = "macho")),
+                ("pe", cfg!(feature = "pe")),
+                ("remote", cfg!(feature = "remote")),
+                ("wasm", cfg!(feature = "wasm")),
+                ("zip", cfg!(feature = "zip")),
+            ]
+            .iter()
+            .copied()
+            .collect(),
+            goblin: GOBLIN_VERSION,
+            version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+}
+impl fmt::Display for Features {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "checksec {} (goblin {})", self.version, self.goblin)?;
+        for (feature, enabled) in &self.features {
+            writeln!(
+                f,
+                "  {:<13}{}",
+                feature,
+                if *enabled { "yes" } else { "no" }
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use checksec::binary::{Binaries, Binary, OUTPUT_VERSION};
+use schemars::schema_for;
+use serde_json::{json, Map, Value};
+
+/// fields of [`Binary`] itself, `type` selects `binarytype`
+const BINARY_FIELDS: [&str; 4] = ["arch", "file", "packed", "type"];
+
+/// every selectable field name, the properties of all format specific
+/// results taken from the json schema so new fields are picked up
+pub fn known() -> Vec<String> {
+    let schema = json!(schema_for!(Binaries));
+    let mut names: Vec<String> =
+        BINARY_FIELDS.iter().map(ToString::to_string).collect();
+    if let Some(definitions) = schema["definitions"].as_object() {
+        for (name, definition) in definitions {
+            if !name.ends_with("CheckSecResults") {
+                continue;
+            }
+            if let Some(properties) = definition["properties"].as_object() {
+                names.extend(properties.keys().cloned());
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// parse a comma separated list of field names, returning the first
+/// unknown name on failure
+pub fn parse(list: &str) -> Result<Vec<String>, String> {
+    let known = known();
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            if known.iter().any(|field| field == name) {
+                Ok(name.to_string())
+            } else {
+                Err(format!(
+                    "unknown field {} [possible values: {}]",
+                    name,
+                    known.join(", ")
+                ))
+            }
+        })
+        .collect()
+}
+
+fn binary_key(field: &str) -> &str {
+    if field == "type" {
+        "binarytype"
+    } else {
+        field
+    }
+}
+
+/// json of `binary` with only the selected `fields`, keeping the layout
+/// of the full output
+///
+/// fields that do not apply to the format of the binary are left out
+pub fn project(binary: &Binary, fields: &[String]) -> Value {
+    let full = json!(binary);
+    let mut projected = Map::new();
+    for field in fields {
+        let key = binary_key(field);
+        if BINARY_FIELDS.contains(&field.as_str()) {
+            if let Some(value) = full.get(key) {
+                projected.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+    // `properties` holds a single object keyed by the binary format
+    if let Some((format, results)) =
+        full["properties"].as_object().and_then(|props| props.iter().next())
+    {
+        let selected: Map<String, Value> = fields
+            .iter()
+            .filter_map(|field| {
+                results.get(field).map(|value| (field.clone(), value.clone()))
+            })
+            .collect();
+        projected.insert(
+            "properties".to_string(),
+            json!({ format.clone(): selected }),
+        );
+    }
+    Value::Object(projected)
+}
+
+/// json of every binary in `bins` restricted to `fields`, wrapped like
+/// the full output
+pub fn project_all(bins: &[Binary], fields: &[String]) -> Value {
+    let binaries: Vec<Value> =
+        bins.iter().map(|bin| project(bin, fields)).collect();
+    json!({ "binaries": binaries, "version": OUTPUT_VERSION })
+}
+
+/// flat text of a json value, `None` for `null`, arrays are joined with
+/// `;`
+fn flatten(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(flatten)
+                .collect::<Vec<String>>()
+                .join(";"),
+        ),
+        // e.g. `{"paths": [...]}` of rpaths
+        Value::Object(object) if object.len() == 1 => {
+            object.values().next().and_then(flatten)
+        }
+        other => Some(other.to_string()),
+    }
+}
+
+/// the selected `fields` of `binary` as text, `None` for fields that do
+/// not apply to its format
+///
+/// `arch` prefers the architecture of the results over the fat `MachO`
+/// slice name
+pub fn values(binary: &Binary, fields: &[String]) -> Vec<Option<String>> {
+    let full = json!(binary);
+    let results = full["properties"]
+        .as_object()
+        .and_then(|props| props.values().next())
+        .cloned()
+        .unwrap_or(Value::Null);
+    fields
+        .iter()
+        .map(|field| {
+            results
+                .get(field)
+                .or_else(|| {
+                    BINARY_FIELDS
+                        .contains(&field.as_str())
+                        .then(|| full.get(binary_key(field)))
+                        .flatten()
+                })
+                .and_then(flatten)
+        })
+        .collect()
+}
+
+/// human readable `field: value` pairs of the selected `fields`, fields
+/// that do not apply to the format of the binary are left out
+pub fn text(binary: &Binary, fields: &[String]) -> String {
+    fields
+        .iter()
+        .zip(values(binary, fields))
+        .filter_map(|(field, value)| {
+            value.map(|value| format!("{}: {}", field, value))
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -0,0 +1,145 @@
+//! Compact overview with one line per binary and one character per
+//! mitigation
+//!
+//! every format has a fixed sequence of flags, a mitigation shows its
+//! letter in uppercase when enabled, in lowercase when partial, `-` when
+//! disabled and `.` when it does not apply. Letters are only ever
+//! appended, so a column keeps its meaning across releases.
+#[cfg(feature = "color")]
+use colored::Colorize;
+
+use checksec::binary::{Binary, Mitigation};
+
+use crate::group::format_name;
+use crate::output::outln;
+
+/// flag letters and mitigations of ELF binaries, in column order
+const ELF_FLAGS: [(char, &str); 12] = [
+    ('P', "pie"),
+    ('N', "nx"),
+    ('R', "relro"),
+    ('C', "canary"),
+    ('F', "fortify"),
+    ('W', "wx"),
+    ('I', "clang_cfi"),
+    ('S', "clang_safestack"),
+    ('T', "ibt"),
+    ('H', "shstk"),
+    ('B', "bti"),
+    ('A', "pac"),
+];
+/// flag letters and mitigations of PE binaries, in column order
+const PE_FLAGS: [(char, &str); 10] = [
+    ('A', "aslr"),
+    ('E', "high_entropy_va"),
+    ('D', "dep"),
+    ('G', "gs"),
+    ('C', "cfg"),
+    ('S', "safeseh"),
+    ('I', "force_integrity"),
+    ('O', "isolation"),
+    ('R', "rfg"),
+    ('K', "authenticode"),
+];
+/// flag letters and mitigations of `MachO` binaries, in column order
+const MACHO_FLAGS: [(char, &str); 8] = [
+    ('P', "pie"),
+    ('N', "nx_stack"),
+    ('X', "nx_heap"),
+    ('C', "canary"),
+    ('F', "fortify"),
+    ('S', "code_signature"),
+    ('H', "hardened_runtime"),
+    ('R', "restrict"),
+];
+
+/// columns of a format, Wasm has no mitigations to show
+fn flags(format: &str) -> &'static [(char, &'static str)] {
+    match format {
+        "ELF" => &ELF_FLAGS,
+        "PE" => &PE_FLAGS,
+        "MachO" => &MACHO_FLAGS,
+        _ => &[],
+    }
+}
+
+/// flag character of a mitigation state
+fn flag(letter: char, state: Mitigation) -> String {
+    let flag = match state {
+        Mitigation::Enabled => letter,
+        Mitigation::Partial => letter.to_ascii_lowercase(),
+        Mitigation::Disabled => '-',
+        Mitigation::NotApplicable => '.',
+    }
+    .to_string();
+    #[cfg(feature = "color")]
+    let flag = match state {
+        Mitigation::Enabled => flag.green().to_string(),
+        Mitigation::Partial => flag.yellow().to_string(),
+        Mitigation::Disabled => flag.red().to_string(),
+        Mitigation::NotApplicable => flag,
+    };
+    flag
+}
+
+/// file name shown for a binary, slices of fat binaries add the
+/// architecture
+fn name(bin: &Binary) -> String {
+    match &bin.arch {
+        Some(arch) => format!("{} ({})", bin.file, arch),
+        None => bin.file.clone(),
+    }
+}
+
+/// print the legend of the formats present followed by one line per
+/// binary
+pub fn print<'a>(bins: impl IntoIterator<Item = &'a Binary>) {
+    let bins: Vec<&Binary> = bins.into_iter().collect();
+    let mut formats: Vec<&str> =
+        bins.iter().map(|bin| format_name(bin)).collect();
+    formats.sort_unstable();
+    formats.dedup();
+    outln!("# UPPER enabled, lower partial, - disabled, . not applicable");
+    for format in &formats {
+        let columns = flags(format);
+        if columns.is_empty() {
+            continue;
+        }
+        let letters: String =
+            columns.iter().map(|(letter, _)| letter).collect();
+        let legend: Vec<String> = columns
+            .iter()
+            .map(|(letter, mitigation)| format!("{}={}", letter, mitigation))
+            .collect();
+        outln!("# {:<5} {} {}", format, letters, legend.join(" "));
+    }
+    let names: Vec<String> = bins.iter().map(|bin| name(bin)).collect();
+    let width = names.iter().map(|name| name.chars().count()).max();
+    for (bin, name) in bins.iter().zip(&names) {
+        let format = format_name(bin);
+        let states = bin.properties.mitigations();
+        let line: String = flags(format)
+            .iter()
+            .map(|(letter, mitigation)| {
+                flag(
+                    *letter,
+                    states
+                        .iter()
+                        .find(|(name, _)| name == mitigation)
+                        .map_or(Mitigation::NotApplicable, |(_, state)| {
+                            *state
+                        }),
+                )
+            })
+            .collect();
+        let row = format!(
+            "{:width$}  {:<5} {}",
+            name,
+            format,
+            line,
+            width = width.unwrap_or_default()
+        );
+        // Wasm rows have no flags
+        outln!("{}", row.trim_end());
+    }
+}
@@ -0,0 +1,93 @@
+use checksec::binary::{BinSpecificProperties, Binary, OUTPUT_VERSION};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::fields;
+
+/// section name of the binary format, also used by `--summary`
+pub fn format_name(binary: &Binary) -> &'static str {
+    match binary.properties {
+        #[cfg(feature = "elf")]
+        BinSpecificProperties::Elf(_) => "ELF",
+        #[cfg(feature = "pe")]
+        BinSpecificProperties::PE(_) => "PE",
+        #[cfg(feature = "macho")]
+        BinSpecificProperties::MachO(_) => "MachO",
+        #[cfg(feature = "wasm")]
+        BinSpecificProperties::Wasm(_) => "Wasm",
+    }
+}
+
+/// position of the binary format in grouped output, ELF before PE before
+/// `MachO` before `WebAssembly`
+fn format_order(binary: &Binary) -> u8 {
+    match binary.properties {
+        #[cfg(feature = "elf")]
+        BinSpecificProperties::Elf(_) => 0,
+        #[cfg(feature = "pe")]
+        BinSpecificProperties::PE(_) => 1,
+        #[cfg(feature = "macho")]
+        BinSpecificProperties::MachO(_) => 2,
+        #[cfg(feature = "wasm")]
+        BinSpecificProperties::Wasm(_) => 3,
+    }
+}
+
+/// binaries of a single format
+#[derive(Serialize)]
+pub struct Group {
+    pub format: &'static str,
+    pub binaries: Vec<Binary>,
+}
+
+/// grouped json/yaml output
+#[derive(Serialize)]
+pub struct Groups {
+    pub groups: Vec<Group>,
+    pub version: u32,
+}
+
+/// split `bins` by binary format, each group sorted by path
+///
+/// the sort is stable so slices of fat `MachO` binaries keep their order
+pub fn by_type(mut bins: Vec<Binary>) -> Groups {
+    bins.sort_by(|a, b| {
+        format_order(a).cmp(&format_order(b)).then_with(|| a.file.cmp(&b.file))
+    });
+    let mut groups: Vec<Group> = Vec::new();
+    for bin in bins {
+        let format = format_name(&bin);
+        match groups.last_mut() {
+            Some(group) if group.format == format => group.binaries.push(bin),
+            _ => groups.push(Group { format, binaries: vec![bin] }),
+        }
+    }
+    Groups { groups, version: OUTPUT_VERSION }
+}
+
+impl Groups {
+    /// json of the groups, restricted to the `selected` fields when given
+    pub fn project(&self, selected: Option<&[String]>) -> Value {
+        let selected = match selected {
+            Some(selected) => selected,
+            None => return json!(self),
+        };
+        let groups: Vec<Value> = self
+            .groups
+            .iter()
+            .map(|group| {
+                let binaries: Vec<Value> = group
+                    .binaries
+                    .iter()
+                    .map(|bin| fields::project(bin, selected))
+                    .collect();
+                json!({ "binaries": binaries, "format": group.format })
+            })
+            .collect();
+        json!({ "groups": groups, "version": self.version })
+    }
+    /// every binary in group order
+    pub fn into_binaries(self) -> Vec<Binary> {
+        self.groups.into_iter().flat_map(|group| group.binaries).collect()
+    }
+}
@@ -0,0 +1,177 @@
+//! Self-contained html report of the scanned binaries
+//!
+//! the style and the script sorting the table are inlined, so the report
+//! can be shared as a single file and opened offline
+use checksec::binary::{mitigation_names, Binary, Mitigation};
+
+use crate::summary;
+
+use std::fmt::Write as _;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.5em; }
+h2 { font-size: 1.2em; margin-top: 1.5em; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; }
+th { background: #eee; }
+table.sortable th { cursor: pointer; user-select: none; }
+th[data-order=asc]::after { content: \" \\25b2\"; }
+th[data-order=desc]::after { content: \" \\25bc\"; }
+td.file { font-family: monospace; }
+.yes { background: #d4f4d4; }
+.partial { background: #fbeec1; }
+.no { background: #f8d0d0; }
+.na { color: #999; }
+";
+
+const SCRIPT: &str = "
+document.querySelectorAll('table.sortable th').forEach(function (th, column) {
+  th.addEventListener('click', function () {
+    var body = th.closest('table').tBodies[0];
+    var ascending = th.dataset.order !== 'asc';
+    th.parentNode.querySelectorAll('th').forEach(function (other) {
+      delete other.dataset.order;
+    });
+    th.dataset.order = ascending ? 'asc' : 'desc';
+    var key = function (row) {
+      var cell = row.cells[column];
+      return cell.dataset.sort || cell.textContent;
+    };
+    Array.from(body.rows)
+      .sort(function (a, b) {
+        var x = key(a), y = key(b);
+        var order = isNaN(x) || isNaN(y) ? x.localeCompare(y) : x - y;
+        return ascending ? order : -order;
+      })
+      .forEach(function (row) { body.appendChild(row); });
+  });
+});
+";
+
+/// escape text for element content and quoted attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// table cell of a mitigation state, ranked so sorting groups the states
+fn cell(state: Mitigation) -> String {
+    let (class, text, rank) = match state {
+        Mitigation::Enabled => ("yes", "yes", 3),
+        Mitigation::Partial => ("partial", "partial", 2),
+        Mitigation::Disabled => ("no", "no", 1),
+        Mitigation::NotApplicable => ("na", "-", 0),
+    };
+    format!("<td class=\"{}\" data-sort=\"{}\">{}</td>", class, rank, text)
+}
+
+/// per format counts of every mitigation, colored by how many binaries
+/// have it enabled
+fn summary_section(bins: &[Binary]) -> String {
+    let mut section = String::from("<h2>Summary</h2>\n");
+    for (format, (binaries, counts)) in &summary::count(bins) {
+        writeln!(section, "<h3>{} ({} binaries)</h3>", format, binaries).ok();
+        section.push_str(
+            "<table>\n<thead><tr><th>mitigation</th><th>enabled</th>\
+             <th>partial</th></tr></thead>\n<tbody>\n",
+        );
+        for (name, count) in counts {
+            if count.total == 0 {
+                continue;
+            }
+            let class = if count.enabled == count.total {
+                "yes"
+            } else if count.enabled + count.partial == 0 {
+                "no"
+            } else {
+                "partial"
+            };
+            writeln!(
+                section,
+                "<tr><td>{}</td><td class=\"{}\">{}/{}</td><td>{}</td></tr>",
+                name, class, count.enabled, count.total, count.partial
+            )
+            .ok();
+        }
+        section.push_str("</tbody>\n</table>\n");
+    }
+    section
+}
+
+/// one sortable row per binary with a column for every mitigation that
+/// applies to at least one of them
+fn binaries_section(bins: &[Binary]) -> String {
+    let mitigations: Vec<Vec<(&str, Mitigation)>> =
+        bins.iter().map(|bin| bin.properties.mitigations()).collect();
+    let columns: Vec<&str> = mitigation_names()
+        .into_iter()
+        .filter(|name| {
+            mitigations.iter().flatten().any(|(mitigation, state)| {
+                mitigation == name
+                    && !matches!(state, Mitigation::NotApplicable)
+            })
+        })
+        .collect();
+    let mut section = String::from(
+        "<h2>Binaries</h2>\n<table class=\"sortable\">\n<thead><tr>\
+         <th>file</th><th>type</th><th>arch</th><th>hardened</th>",
+    );
+    for column in &columns {
+        write!(section, "<th>{}</th>", column).ok();
+    }
+    section.push_str("</tr></thead>\n<tbody>\n");
+    for (bin, states) in bins.iter().zip(&mitigations) {
+        write!(
+            section,
+            "<tr><td class=\"file\">{}</td><td>{}</td><td>{}</td>{}",
+            escape(&bin.file),
+            bin.binarytype,
+            escape(bin.arch.as_deref().unwrap_or("")),
+            cell(if bin.is_fully_hardened() {
+                Mitigation::Enabled
+            } else {
+                Mitigation::Disabled
+            })
+        )
+        .ok();
+        for column in &columns {
+            section.push_str(&cell(
+                states
+                    .iter()
+                    .find(|(name, _)| name == column)
+                    .map_or(Mitigation::NotApplicable, |(_, state)| *state),
+            ));
+        }
+        section.push_str("</tr>\n");
+    }
+    section.push_str("</tbody>\n</table>\n");
+    section
+}
+
+/// html document with a summary per format followed by the binaries
+pub fn report(bins: &[Binary]) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n\
+         <meta charset=\"utf-8\">\n<title>checksec report</title>\n<style>{}</style>\n</head>\n<body>\n\
+         <h1>checksec report</h1>\n<p>{} binaries checked with checksec {}, \
+         click a column header to sort.</p>\n{}{}<script>{}</script>\n\
+         </body>\n</html>\n",
+        STYLE,
+        bins.len(),
+        env!("CARGO_PKG_VERSION"),
+        summary_section(bins),
+        binaries_section(bins),
+        SCRIPT
+    )
+}
@@ -2,7 +2,8 @@
 //!
 //! Checksec is a standalone command line utility and library that provides
 //! binary executable security-oriented property checks for `ELF`, `PE`, and
-//! `MachO`executables.
+//! `MachO`executables. `WebAssembly` modules are recognized and their
+//! imports and exports summarized.
 //!
 //! **Structures**
 //!
@@ -11,14 +12,15 @@
 //! [`goblin::Object`](https://docs.rs/goblin/latest/goblin/enum.Object.html)
 //! object to the parse method.
 //!
-//! * [`checksec::elf::ElfCheckSecResults`](./elf/struct.ElfCheckSecResults.html)
-//! * [`checksec::macho::MachOCheckSecResults`](./macho/struct.MachOCheckSecResults.html)
-//! * [`checksec::pe::PECheckSecResults`](./pe/struct.PECheckSecResults.html)
+//! * [`checksec::elf::CheckSecResults`](./elf/struct.CheckSecResults.html)
+//! * [`checksec::macho::CheckSecResults`](./macho/struct.CheckSecResults.html)
+//! * [`checksec::pe::CheckSecResults`](./pe/struct.CheckSecResults.html)
+//! * [`checksec::wasm::CheckSecResults`](./wasm/struct.CheckSecResults.html)
 //!
 //! ```rust
-//! use checksec::elf::ElfCheckSecResults;
-//! use checksec::macho::MachOCheckSecResults;
-//! use checksec::pe::PECheckSecResults;
+//! use checksec::elf::CheckSecResults as ElfCheckSecResults;
+//! use checksec::macho::CheckSecResults as MachOCheckSecResults;
+//! use checksec::pe::CheckSecResults as PECheckSecResults;
 //! ```
 //!
 //! **Traits**
@@ -27,14 +29,45 @@
 //! have direct access to the security property check functions for a given
 //! binary executable format.
 //!
-//! * [`checksec::elf::ElfProperties`](./elf/trait.ElfProperties.html)
+//! * [`checksec::elf::Properties`](./elf/trait.Properties.html)
 //! * [`checksec::macho::MachOProperties`](./macho/trait.MachOProperties.html)
-//! * [`checksec::pe::PEProperties`](./pe/trait.PEProperties.html)
+//! * [`checksec::pe::Properties`](./pe/trait.Properties.html)
 //!
 //! ```rust
-//! use checksec::elf::ElfProperties;
+//! use checksec::elf::Properties as ElfProperties;
 //! use checksec::macho::MachOProperties;
-//! use checksec::pe::PEProperties;
+//! use checksec::pe::Properties as PEProperties;
+//! ```
+//!
+//! **Scanning files**
+//!
+//! [`parse_path`](./binary/fn.parse_path.html) detects the format of a file
+//! and returns the results wrapped in
+//! [`binary::Binary`](./binary/struct.Binary.html), the same structure the
+//! command line utility prints and serializes.
+//!
+//! ```rust
+//! use std::path::Path;
+//!
+//! if let Ok(binaries) = checksec::parse_path(Path::new("/bin/ls")) {
+//!     for binary in &binaries {
+//!         println!("{}", binary);
+//!     }
+//! }
+//! ```
+//!
+//! [`scan_dir`](./scan/fn.scan_dir.html) walks a directory tree and yields
+//! the results of each file lazily, see the [`scan`](./scan/index.html)
+//! module for checking files in parallel.
+//!
+//! ```rust,no_run
+//! use checksec::{scan_dir, ScanOptions};
+//! use std::path::Path;
+//!
+//! let options = ScanOptions { max_depth: Some(1), ..ScanOptions::default() };
+//! for binary in scan_dir(Path::new("/usr/bin"), &options).flatten() {
+//!     println!("{}", binary);
+//! }
 //! ```
 //!
 //! Refer to the generated docs or the examples directory
@@ -42,13 +75,23 @@
 //! for examples of working with both `*Properties` traits and
 //! `*CheckSecResults` structs.
 //!
+pub mod binary;
 #[cfg(feature = "elf")]
 pub mod elf;
+pub mod entropy;
 #[cfg(feature = "macho")]
 pub mod macho;
 pub mod macros;
 #[cfg(feature = "pe")]
 pub mod pe;
+pub mod scan;
 #[cfg(feature = "shared")]
 #[macro_use]
 pub mod shared;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use binary::{
+    parse_bytes, parse_path, parse_path_hashed, parse_path_with_limit,
+};
+pub use scan::{scan_dir, ScanOptions};
@@ -1,18 +1,198 @@
 //! Implements checksec for `MachO` binaries
 #[cfg(feature = "color")]
 use colored::Colorize;
+use goblin::mach::constants::cputype::get_arch_name_from_types;
+use goblin::mach::header::{
+    MH_BUNDLE, MH_CORE, MH_DSYM, MH_DYLIB, MH_DYLIB_STUB, MH_DYLINKER,
+    MH_EXECUTE, MH_KEXT_BUNDLE, MH_OBJECT, MH_PRELOAD,
+};
 use goblin::mach::load_command::CommandVariant;
 use goblin::mach::MachO;
+use schemars::JsonSchema;
+use scroll::{Endian, Pread as _, BE};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 #[cfg(feature = "color")]
 use crate::colorize_bool;
-//use crate::shared::{Rpath, VecRpath};
+use crate::entropy::{self, SectionEntropy};
+use crate::shared::{Rpath, VecRpath};
 
 const MH_ALLOW_STACK_EXECUTION: u32 = 0x0002_0000;
 const MH_PIE: u32 = 0x0020_0000;
 const MH_NO_HEAP_EXECUTION: u32 = 0x0100_0000;
+/// kernel collection of kexts, missing from goblin
+const MH_FILESET: u32 = 0xc;
+
+/// Objective-C runtime entry points emitted by the compiler under ARC
+const ARC_IMPORTS: [&str; 2] =
+    ["_objc_release", "_objc_retainAutoreleasedReturnValue"];
+
+// code signature blobs are always stored big endian
+const CSMAGIC_EMBEDDED_SIGNATURE: u32 = 0xfade_0cc0;
+const CSMAGIC_CODEDIRECTORY: u32 = 0xfade_0c02;
+const CSSLOT_CODEDIRECTORY: u32 = 0;
+const CS_RUNTIME: u32 = 0x0001_0000;
+#[cfg(feature = "entitlements")]
+const CSMAGIC_EMBEDDED_ENTITLEMENTS: u32 = 0xfade_7171;
+#[cfg(feature = "entitlements")]
+const CSSLOT_ENTITLEMENTS: u32 = 5;
+
+/// entitlements that weaken code signing or runtime protections
+#[cfg(feature = "entitlements")]
+pub const RISKY_ENTITLEMENTS: [&str; 7] = [
+    "com.apple.security.cs.allow-dyld-environment-variables",
+    "com.apple.security.cs.allow-jit",
+    "com.apple.security.cs.allow-unsigned-executable-memory",
+    "com.apple.security.cs.disable-executable-page-protection",
+    "com.apple.security.cs.disable-library-validation",
+    "com.apple.security.get-task-allow",
+    "get-task-allow",
+];
+
+/// offset of the blob in code signature `slot` relative to `signature`,
+/// `None` when the signature has no such slot
+fn code_signature_blob(
+    mem: &[u8],
+    signature: usize,
+    slot: u32,
+) -> Option<usize> {
+    let read = |offset: usize| -> Option<u32> {
+        mem.pread_with(signature.checked_add(offset)?, BE).ok()
+    };
+    if read(0)? != CSMAGIC_EMBEDDED_SIGNATURE {
+        return None;
+    }
+    // a corrupt count can not make the scan go past the end of the file
+    let fits = mem.len().saturating_sub(signature).saturating_sub(12) / 8;
+    let count = (read(8)? as usize).min(fits);
+    (0..count)
+        // `CS_BlobIndex` entries follow the 12 byte `CS_SuperBlob`
+        .map(|idx| 12 + idx * 8)
+        .find(|entry| read(*entry) == Some(slot))
+        .and_then(|entry| read(entry + 4))
+        .map(|offset| offset as usize)
+}
+
+/// top level `<key>` names of an xml property list
+///
+/// a deliberately small scanner, entitlements are plain dictionaries so
+/// neither a plist nor an xml parser is pulled in for them
+#[cfg(feature = "entitlements")]
+fn plist_keys(plist: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut depth = 0_usize;
+    let mut rest = plist;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let end = match rest.find('>') {
+            Some(end) => end,
+            None => break,
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        match tag {
+            "dict" => depth += 1,
+            "/dict" => depth = depth.saturating_sub(1),
+            "key" if depth == 1 => {
+                if let Some(close) = rest.find("</key>") {
+                    keys.push(rest[..close].trim().to_string());
+                    rest = &rest[close..];
+                }
+            }
+            _ => {}
+        }
+    }
+    keys
+}
+
+/// `LC_BUILD_VERSION`, not decoded by goblin
+const LC_BUILD_VERSION: u32 = 0x32;
+
+/// format a `xxxx.yy.zz` nibble encoded version as `major.minor`, with the
+/// patch level appended when it is set
+fn format_version(version: u32) -> String {
+    let (major, minor, patch) =
+        (version >> 16, (version >> 8) & 0xff, version & 0xff);
+    if patch == 0 {
+        format!("{}.{}", major, minor)
+    } else {
+        format!("{}.{}.{}", major, minor, patch)
+    }
+}
+
+/// `filetype` of the `MachO` header
+///
+/// the stack and heap execution flags are only honored for the main
+/// executable, and dyld always slides the other images
+#[derive(Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileType {
+    Bundle,
+    Core,
+    Dsym,
+    Dylib,
+    DylibStub,
+    Dylinker,
+    Execute,
+    Fileset,
+    KextBundle,
+    Object,
+    Preload,
+    Unknown,
+}
+impl FileType {
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match *self {
+            Self::Bundle => "bundle",
+            Self::Core => "core",
+            Self::Dsym => "dsym",
+            Self::Dylib => "dylib",
+            Self::DylibStub => "dylib-stub",
+            Self::Dylinker => "dylinker",
+            Self::Execute => "execute",
+            Self::Fileset => "fileset",
+            Self::KextBundle => "kext-bundle",
+            Self::Object => "object",
+            Self::Preload => "preload",
+            Self::Unknown => "unknown",
+        }
+    }
+    /// images loaded into another process, always position independent
+    #[must_use]
+    pub fn is_library(&self) -> bool {
+        matches!(
+            self,
+            Self::Bundle | Self::Dylib | Self::Dylinker | Self::KextBundle
+        )
+    }
+}
+impl fmt::Display for FileType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<11}", self.name())
+    }
+}
+
+/// location of the code signature blob from `LC_CODE_SIGNATURE`
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct CodeSignature {
+    /// file offset of the signature in the `__LINKEDIT` segment
+    pub dataoff: u32,
+    /// size of the signature in the `__LINKEDIT` segment
+    pub datasize: u32,
+}
+
+/// encrypted range from `LC_ENCRYPTION_INFO`/`LC_ENCRYPTION_INFO_64`
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct EncryptionInfo {
+    /// encryption system, `0` when the range is not encrypted
+    pub cryptid: u32,
+    /// file offset of the encrypted range
+    pub cryptoff: u32,
+    /// size of the encrypted range
+    pub cryptsize: u32,
+}
 
 /// Checksec result struct for `MachO32/64` binaries
 ///
@@ -25,57 +205,159 @@ const MH_NO_HEAP_EXECUTION: u32 = 0x0100_0000;
 ///
 /// pub fn print_results(binary: &String) {
 ///     if let Ok(fp) = fs::File::open(&binary) {
-///         if let Ok(buf) = fs::read(fp) {
-///             if let Ok(macho) = MachO::parse(&buf) {
-///                 println!("{:#?}", CheckSecResults::parse(&macho));
+///         if let Ok(buf) = fs::read(&binary) {
+///             if let Ok(macho) = MachO::parse(&buf, 0) {
+///                 println!("{:#?}", CheckSecResults::parse(&macho, &buf));
 ///             }
 ///         }
 ///     }
 /// }
 /// ```
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+#[schemars(rename = "MachOCheckSecResults")]
 pub struct CheckSecResults {
-    /// Automatic Reference Counting
+    /// Automatic Reference Counting *(heuristic, not a security mitigation)*
     pub arc: bool,
+    /// CPU architecture (`cputype`/`cpusubtype` of the `MachO` header)
+    pub arch: String,
     /// Stack Canary
     pub canary: bool,
     /// Code Signature (codesign)
     pub code_signature: bool,
+    /// `LC_CODE_SIGNATURE` load command
+    pub code_signature_location: Option<CodeSignature>,
     /// Encrypted (`LC_ENCRYPTION_INFO`/`LC_ENCRYPTION_INFO_64`)
     pub encrypted: bool,
+    /// `MachO` header file type, e.g. `execute`, `dylib` or `kext-bundle`
+    pub filetype: FileType,
+    /// entitlement keys from the embedded code signature
+    #[cfg(feature = "entitlements")]
+    pub entitlements: Option<Vec<String>>,
+    /// `LC_ENCRYPTION_INFO`/`LC_ENCRYPTION_INFO_64` load command
+    pub encryption_info: Option<EncryptionInfo>,
     /// Fortify (*CFLAGS=*`-D_FORTIFY_SOURCE`)
     pub fortify: bool,
     /// Fortified functions
     pub fortified: u32,
+    /// Hardened Runtime (`CS_RUNTIME` code signature flag), supersedes the
+    /// restrict segment on newer macOS releases
+    pub hardened_runtime: bool,
+    /// Minimum OS version (`LC_BUILD_VERSION`/`LC_VERSION_MIN_*`)
+    pub min_os: Option<String>,
     /// Non-Executable Heap (`MH_NO_HEAP_EXECUTION`)
     pub nx_heap: bool,
     /// Non-Executable Stack (`MH_ALLOW_STACK_EXECUTION`)
     pub nx_stack: bool,
-    /// Position Independent Executable (`MH_PIE`)
+    /// Position Independent Executable (`MH_PIE`), always set for
+    /// libraries, bundles and kexts which are slid by their loader
     pub pie: bool,
-    /// Restrict segment
+    /// Restrict segment (`__RESTRICT,__restrict`), used by older binaries
+    /// to make dyld ignore `DYLD_*` environment variables
     pub restrict: bool,
     /// Load Command @rpath
-    //rpath: VecRpath,
-    pub rpath: bool,
+    pub rpath: VecRpath,
+    /// SDK version (`LC_BUILD_VERSION`/`LC_VERSION_MIN_*`)
+    pub sdk: Option<String>,
+    /// entropy of the contents of every section, named `segment,section`,
+    /// empty after [`crate::binary::Binary::strip_section_entropy`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub section_entropy: Vec<SectionEntropy>,
+    /// likely misconfigurations that make the binary look more hardened
+    /// than it is, e.g. a PIE with an executable stack
+    pub warnings: Vec<String>,
 }
 impl CheckSecResults {
+    /// `buffer` holds the bytes of this `MachO` image, for fat binaries
+    /// only the architecture slice
     #[must_use]
-    pub fn parse(macho: &MachO) -> Self {
-        Self {
+    pub fn parse(macho: &MachO, buffer: &[u8]) -> Self {
+        let version = macho.get_version_min(buffer);
+        let filetype = macho.get_filetype();
+        let pie = macho.has_pie() || filetype.is_library();
+        let mut results = Self {
             arc: macho.has_arc(),
+            arch: macho.get_arch(),
             canary: macho.has_canary(),
             code_signature: macho.has_code_signature(),
+            code_signature_location: macho.get_code_signature(),
             encrypted: macho.has_encrypted(),
+            #[cfg(feature = "entitlements")]
+            entitlements: macho.get_entitlements(buffer),
+            encryption_info: macho.get_encryption_info(),
+            filetype,
             fortify: macho.has_fortify(),
             fortified: macho.has_fortified(),
+            hardened_runtime: macho.has_hardened_runtime(buffer),
+            min_os: version.map(|(min_os, _)| format_version(min_os)),
             nx_heap: macho.has_nx_heap(),
             nx_stack: macho.has_nx_stack(),
-            pie: macho.has_pie(),
+            pie,
             restrict: macho.has_restrict(),
             rpath: macho.has_rpath(),
+            sdk: version.map(|(_, sdk)| format_version(sdk)),
+            section_entropy: macho.get_section_entropy(),
+            warnings: Vec::new(),
+        };
+        results.warnings = results.derive_warnings();
+        results
+    }
+    /// advisory checks combining the results of the base checks
+    fn derive_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.pie && !self.nx_stack {
+            warnings.push(
+                "position independent but the stack is executable".to_string(),
+            );
+        }
+        if self.restrict && !self.code_signature {
+            warnings.push(
+                "__RESTRICT segment without a code signature".to_string(),
+            );
         }
+        warnings
+    }
+    /// entitlements from [`RISKY_ENTITLEMENTS`] this binary is signed with
+    #[cfg(feature = "entitlements")]
+    #[must_use]
+    pub fn risky_entitlements(&self) -> Vec<&str> {
+        self.entitlements
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|key| RISKY_ENTITLEMENTS.contains(key))
+            .collect()
+    }
+    /// supporting detail for verbose output as `(label, evidence)` pairs
+    #[must_use]
+    pub fn evidence(&self) -> Vec<(&'static str, String)> {
+        let mut evidence = Vec::new();
+        if self.filetype.is_library() {
+            evidence.push((
+                "PIE",
+                format!("{} images are always slid", self.filetype.name()),
+            ));
+        }
+        if let Some(signature) = &self.code_signature_location {
+            evidence.push((
+                "Code Signature",
+                format!(
+                    "LC_CODE_SIGNATURE at {:#x} ({:#x} bytes)",
+                    signature.dataoff, signature.datasize
+                ),
+            ));
+        }
+        if let Some(info) = &self.encryption_info {
+            evidence.push((
+                "Encrypted",
+                format!(
+                    "cryptid {} at {:#x} ({:#x} bytes)",
+                    info.cryptid, info.cryptoff, info.cryptsize
+                ),
+            ));
+        }
+        evidence.extend(entropy::evidence(&self.section_entropy));
+        evidence
     }
 }
 
@@ -85,21 +367,37 @@ impl fmt::Display for CheckSecResults {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "ARC: {} Canary: {} Code Signature: {} Encryption: {} \
-            Fortify: {} Fortified {:2} NX Heap: {} \
-            NX Stack: {} PIE: {} Restrict: {} RPath: {}",
+            "ARC: {} Canary: {} Code Signature: {} Encrypted: {} \
+            Filetype: {} Fortify: {} Fortified: {} Hardened Runtime: {} \
+            Min OS: {} NX Heap: {} NX Stack: {} PIE: {} Restrict: {} \
+            RPath: {} SDK: {}",
             self.arc,
             self.canary,
             self.code_signature,
             self.encrypted,
+            self.filetype,
             self.fortify,
             self.fortified,
+            self.hardened_runtime,
+            self.min_os.as_deref().unwrap_or("None"),
             self.nx_heap,
             self.nx_stack,
             self.pie,
             self.restrict,
-            self.rpath
-        )
+            self.rpath,
+            self.sdk.as_deref().unwrap_or("None")
+        )?;
+        #[cfg(feature = "entitlements")]
+        {
+            let risky = self.risky_entitlements();
+            if !risky.is_empty() {
+                write!(f, " Risky Entitlements: {}", risky.join(", "))?;
+            }
+        }
+        if !self.warnings.is_empty() {
+            write!(f, " Warnings: {}", self.warnings.join("; "))?;
+        }
+        Ok(())
     }
     #[cfg(feature = "color")]
     /// Colorized human readable format output
@@ -107,7 +405,7 @@ impl fmt::Display for CheckSecResults {
         write!(
             f,
             "{} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} \
-            {} {} {} {} {} {}",
+            {} {} {} {} {} {} {} {} {} {} {} {} {} {}",
             "ARC:".bold(),
             colorize_bool!(self.arc),
             "Canary:".bold(),
@@ -116,10 +414,16 @@ impl fmt::Display for CheckSecResults {
             colorize_bool!(self.code_signature),
             "Encrypted:".bold(),
             colorize_bool!(self.encrypted),
+            "Filetype:".bold(),
+            self.filetype,
             "Fortify:".bold(),
             colorize_bool!(self.fortify),
             "Fortified:".bold(),
             self.fortified,
+            "Hardened Runtime:".bold(),
+            colorize_bool!(self.hardened_runtime),
+            "Min OS:".bold(),
+            self.min_os.as_deref().unwrap_or("None"),
             "NX Heap:".bold(),
             colorize_bool!(self.nx_heap),
             "NX Stack:".bold(),
@@ -129,9 +433,31 @@ impl fmt::Display for CheckSecResults {
             "Restrict:".bold(),
             colorize_bool!(self.restrict),
             "RPath:".bold(),
-            //self.rpath
-            colorize_bool!(self.rpath)
-        )
+            self.rpath,
+            "SDK:".bold(),
+            self.sdk.as_deref().unwrap_or("None")
+        )?;
+        #[cfg(feature = "entitlements")]
+        {
+            let risky = self.risky_entitlements();
+            if !risky.is_empty() {
+                write!(
+                    f,
+                    " {} {}",
+                    "Risky Entitlements:".bold(),
+                    risky.join(", ").yellow()
+                )?;
+            }
+        }
+        if !self.warnings.is_empty() {
+            write!(
+                f,
+                " {} {}",
+                "Warnings:".bold(),
+                self.warnings.join("; ").red()
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -147,8 +473,8 @@ impl fmt::Display for CheckSecResults {
 ///
 /// pub fn print_results(binary: &String) {
 ///     if let Ok(fp) = fs::File::open(&binary) {
-///         if let Ok(buf) = fs::read(fp) {
-///             if let Ok(macho) = MachO::parse(&buf) {
+///         if let Ok(buf) = fs::read(&binary) {
+///             if let Ok(macho) = MachO::parse(&buf, 0) {
 ///                 println!("arc: {}", macho.has_arc());
 ///             }
 ///         }
@@ -156,7 +482,11 @@ impl fmt::Display for CheckSecResults {
 /// }
 /// ```
 pub trait MachOProperties {
-    /// check import names for `_objc_release`
+    /// check import names for `_objc_release` or
+    /// `_objc_retainAutoreleasedReturnValue`
+    ///
+    /// this is a heuristic, manual reference counting code calling into
+    /// the runtime directly imports the same symbols
     fn has_arc(&self) -> bool;
     /// check import names for `___stack_chk_fail` or `___stack_chk_guard`
     fn has_canary(&self) -> bool;
@@ -169,6 +499,13 @@ pub trait MachOProperties {
     fn has_fortify(&self) -> bool;
     /// count symbols ending in `_chk` from symbols
     fn has_fortified(&self) -> u32;
+    /// check for the `CS_RUNTIME` *(0x00010000)* flag in the `CodeDirectory`
+    /// of the embedded code signature
+    ///
+    /// requires the raw bytes of the image *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// as the signature is not part of the parsed load commands
+    fn has_hardened_runtime(&self, mem: &[u8]) -> bool;
     /// check `MH_NO_HEAP_EXECUTION` *(0x01000000)* in `MachO` header flags
     fn has_nx_heap(&self) -> bool;
     /// check `MH_ALLOW_STACK_EXECUTION` *(0x00020000)* in `MachO` header flags
@@ -177,15 +514,41 @@ pub trait MachOProperties {
     fn has_pie(&self) -> bool;
     /// check for `___restrict` segment name
     fn has_restrict(&self) -> bool;
-    //fn has_rpath(&self) -> VecRpath;
-    /// check for `RPath` in load commands
-    fn has_rpath(&self) -> bool;
+    /// collect `LC_RPATH` entries from load commands into `VecRpath`
+    fn has_rpath(&self) -> VecRpath;
+    /// map `cputype`/`cpusubtype` of the `MachO` header to a readable
+    /// architecture name
+    fn get_arch(&self) -> String;
+    /// get the signature offset and size from `LC_CODE_SIGNATURE` in load
+    /// commands
+    fn get_code_signature(&self) -> Option<CodeSignature>;
+    /// get the encrypted range from EncryptionInfo32/64 in load commands
+    fn get_encryption_info(&self) -> Option<EncryptionInfo>;
+    /// get the top level keys of the entitlements plist in the embedded
+    /// code signature
+    ///
+    /// requires the raw bytes of the image, like
+    /// [`MachOProperties::has_hardened_runtime`]
+    #[cfg(feature = "entitlements")]
+    fn get_entitlements(&self, mem: &[u8]) -> Option<Vec<String>>;
+    /// map the `filetype` of the `MachO` header to a [`FileType`]
+    fn get_filetype(&self) -> FileType;
+    /// compute the entropy of the file contents of every section in the
+    /// `LC_SEGMENT`/`LC_SEGMENT_64` load commands, zero fill sections have
+    /// no contents and are left out
+    fn get_section_entropy(&self) -> Vec<SectionEntropy>;
+    /// get the encoded minimum OS and SDK versions from the first
+    /// `LC_BUILD_VERSION` or `LC_VERSION_MIN_*` load command
+    ///
+    /// requires the raw bytes of the image as goblin does not decode
+    /// `LC_BUILD_VERSION`
+    fn get_version_min(&self, mem: &[u8]) -> Option<(u32, u32)>;
 }
 impl MachOProperties for MachO<'_> {
     fn has_arc(&self) -> bool {
         if let Ok(imports) = self.imports() {
             for import in &imports {
-                if import.name == "_objc_release" {
+                if ARC_IMPORTS.contains(&import.name) {
                     return true;
                 }
             }
@@ -204,33 +567,11 @@ impl MachOProperties for MachO<'_> {
         false
     }
     fn has_code_signature(&self) -> bool {
-        for loadcmd in &self.load_commands {
-            if let CommandVariant::CodeSignature(cmd) = loadcmd.command {
-                // just check for existence, todo full validation
-                if cmd.datasize > 0 {
-                    return true;
-                }
-            }
-        }
-        false
+        // just check for existence, todo full validation
+        matches!(self.get_code_signature(), Some(sig) if sig.datasize > 0)
     }
     fn has_encrypted(&self) -> bool {
-        for loadcmd in &self.load_commands {
-            match loadcmd.command {
-                CommandVariant::EncryptionInfo32(cmd) => {
-                    if cmd.cryptid != 0 {
-                        return true;
-                    }
-                }
-                CommandVariant::EncryptionInfo64(cmd) => {
-                    if cmd.cryptid != 0 {
-                        return true;
-                    }
-                }
-                _ => (),
-            }
-        }
-        false
+        matches!(self.get_encryption_info(), Some(info) if info.cryptid != 0)
     }
     fn has_fortify(&self) -> bool {
         for sym in self.symbols().flatten() {
@@ -249,6 +590,23 @@ impl MachOProperties for MachO<'_> {
         }
         fortified_count
     }
+    fn has_hardened_runtime(&self, mem: &[u8]) -> bool {
+        let signature = match self.get_code_signature() {
+            Some(signature) => signature.dataoff as usize,
+            None => return false,
+        };
+        let read = |offset: usize| -> Option<u32> {
+            mem.pread_with(signature.checked_add(offset)?, BE).ok()
+        };
+        match code_signature_blob(mem, signature, CSSLOT_CODEDIRECTORY) {
+            Some(directory) => {
+                read(directory) == Some(CSMAGIC_CODEDIRECTORY)
+                    && read(directory + 12)
+                        .is_some_and(|flags| flags & CS_RUNTIME != 0)
+            }
+            None => false,
+        }
+    }
     fn has_nx_heap(&self) -> bool {
         matches!(self.header.flags & MH_NO_HEAP_EXECUTION, x if x != 0)
     }
@@ -268,18 +626,119 @@ impl MachOProperties for MachO<'_> {
         }
         false
     }
-    //fn has_rpath(&self) -> VecRpath {
-    fn has_rpath(&self) -> bool {
-        // simply check for existence of @rpath command for now
-        // parse out rpath entries similar to elf later
-        // paths separated by `;` instead of `:` like the elf counterpart
-        for loadcmd in &self.load_commands {
-            if let CommandVariant::Rpath(_) = loadcmd.command {
-                return true;
-                //return VecRpath::new(vec![Rpath::Yes("true".to_string())]);
+    fn has_rpath(&self) -> VecRpath {
+        // unlike the elf counterpart every LC_RPATH holds a single path
+        if self.rpaths.is_empty() {
+            return VecRpath::new(vec![Rpath::None]);
+        }
+        VecRpath::new(
+            self.rpaths.iter().map(|path| Rpath::from_macho(path)).collect(),
+        )
+    }
+    fn get_arch(&self) -> String {
+        get_arch_name_from_types(self.header.cputype, self.header.cpusubtype)
+            .map_or_else(
+                || format!("unknown ({:#x})", self.header.cputype),
+                str::to_string,
+            )
+    }
+    fn get_code_signature(&self) -> Option<CodeSignature> {
+        self.load_commands.iter().find_map(|loadcmd| {
+            if let CommandVariant::CodeSignature(cmd) = loadcmd.command {
+                Some(CodeSignature {
+                    dataoff: cmd.dataoff,
+                    datasize: cmd.datasize,
+                })
+            } else {
+                None
             }
+        })
+    }
+    fn get_encryption_info(&self) -> Option<EncryptionInfo> {
+        self.load_commands.iter().find_map(|loadcmd| match loadcmd.command {
+            CommandVariant::EncryptionInfo32(cmd) => Some(EncryptionInfo {
+                cryptid: cmd.cryptid,
+                cryptoff: cmd.cryptoff,
+                cryptsize: cmd.cryptsize,
+            }),
+            CommandVariant::EncryptionInfo64(cmd) => Some(EncryptionInfo {
+                cryptid: cmd.cryptid,
+                cryptoff: cmd.cryptoff,
+                cryptsize: cmd.cryptsize,
+            }),
+            _ => None,
+        })
+    }
+    #[cfg(feature = "entitlements")]
+    fn get_entitlements(&self, mem: &[u8]) -> Option<Vec<String>> {
+        let signature = self.get_code_signature()?.dataoff as usize;
+        let blob = signature.checked_add(code_signature_blob(
+            mem,
+            signature,
+            CSSLOT_ENTITLEMENTS,
+        )?)?;
+        if mem.pread_with::<u32>(blob, BE).ok()?
+            != CSMAGIC_EMBEDDED_ENTITLEMENTS
+        {
+            return None;
         }
-        //VecRpath::new(vec![Rpath::None])
-        false
+        // the length includes the 8 byte blob header
+        let length = mem.pread_with::<u32>(blob + 4, BE).ok()? as usize;
+        let plist = mem.get(blob + 8..blob.checked_add(length)?)?;
+        Some(plist_keys(&String::from_utf8_lossy(plist)))
+    }
+    fn get_filetype(&self) -> FileType {
+        match self.header.filetype {
+            MH_BUNDLE => FileType::Bundle,
+            MH_CORE => FileType::Core,
+            MH_DSYM => FileType::Dsym,
+            MH_DYLIB => FileType::Dylib,
+            MH_DYLIB_STUB => FileType::DylibStub,
+            MH_DYLINKER => FileType::Dylinker,
+            MH_EXECUTE => FileType::Execute,
+            MH_FILESET => FileType::Fileset,
+            MH_KEXT_BUNDLE => FileType::KextBundle,
+            MH_OBJECT => FileType::Object,
+            MH_PRELOAD => FileType::Preload,
+            _ => FileType::Unknown,
+        }
+    }
+    fn get_section_entropy(&self) -> Vec<SectionEntropy> {
+        self.segments
+            .iter()
+            .filter_map(|segment| segment.sections().ok())
+            .flatten()
+            .filter(|(_, data)| !data.is_empty())
+            .map(|(section, data)| {
+                let name = format!(
+                    "{},{}",
+                    section.segname().unwrap_or_default(),
+                    section.name().unwrap_or_default()
+                );
+                SectionEntropy::new(name, data)
+            })
+            .collect()
+    }
+    fn get_version_min(&self, mem: &[u8]) -> Option<(u32, u32)> {
+        let endian =
+            if self.little_endian { Endian::Little } else { Endian::Big };
+        self.load_commands.iter().find_map(|loadcmd| match loadcmd.command {
+            CommandVariant::VersionMinMacosx(cmd)
+            | CommandVariant::VersionMinIphoneos(cmd)
+            | CommandVariant::VersionMinTvos(cmd)
+            | CommandVariant::VersionMinWatchos(cmd) => {
+                Some((cmd.version, cmd.sdk))
+            }
+            CommandVariant::Unimplemented(cmd)
+                if cmd.cmd == LC_BUILD_VERSION =>
+            {
+                // cmd, cmdsize and platform precede minos and sdk
+                let minos =
+                    mem.pread_with(loadcmd.offset + 12, endian).ok()?;
+                let sdk = mem.pread_with(loadcmd.offset + 16, endian).ok()?;
+                Some((minos, sdk))
+            }
+            _ => None,
+        })
     }
 }
@@ -7,7 +7,47 @@ macro_rules! colorize_bool {
             format!("{:<5}", $tf).bright_green().to_string()
         } else {
             format!("{:<5}", $tf).red().to_string()
-        };
+        }
+    };
+}
+
+/// dirty hack to return colorized boolean result as a String for checks
+/// where `true` denotes a weakness
+#[macro_export]
+#[cfg(feature = "color")]
+macro_rules! colorize_bool_inverse {
+    ($tf:expr) => {
+        if $tf {
+            format!("{:<5}", $tf).red().to_string()
+        } else {
+            format!("{:<5}", $tf).bright_green().to_string()
+        }
+    };
+}
+
+/// dirty hack to return colorized optional boolean result as a String,
+/// `None` denotes a check that does not apply to the binary (`N/A`)
+#[macro_export]
+#[cfg(feature = "color")]
+macro_rules! colorize_option_bool {
+    ($tf:expr) => {
+        match $tf {
+            Some(tf) => $crate::colorize_bool!(tf),
+            None => format!("{:<5}", "N/A").dimmed().to_string(),
+        }
+    };
+}
+
+/// return optional boolean result as a String, `None` denotes a check that
+/// does not apply to the binary (`N/A`)
+#[macro_export]
+#[cfg(not(feature = "color"))]
+macro_rules! option_bool {
+    ($tf:expr) => {
+        match $tf {
+            Some(tf) => tf.to_string(),
+            None => "N/A".to_string(),
+        }
     };
 }
 
@@ -1,177 +1,1160 @@
 extern crate clap;
+extern crate glob;
 extern crate goblin;
 extern crate ignore;
+extern crate rayon;
 extern crate serde_json;
+extern crate serde_yaml;
 extern crate sysinfo;
 
-use clap::{crate_authors, crate_description, crate_version, App, Arg};
+use clap::{
+    crate_authors, crate_description, crate_version, App, AppSettings, Arg,
+    ArgGroup, SubCommand,
+};
 use goblin::error::Error;
-#[cfg(feature = "macho")]
-use goblin::mach::{Mach, MachO};
-use goblin::Object;
-use ignore::Walk;
-use memmap::Mmap;
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::WalkBuilder;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use rayon::prelude::*;
+use schemars::schema_for;
+use serde::Serialize;
 #[cfg(not(feature = "color"))]
 use serde_json::to_string_pretty;
 use serde_json::{json, Value};
-use sysinfo::{ProcessExt, RefreshKind, System, SystemExt};
+use sysinfo::{Pid, ProcessExt, RefreshKind, System, SystemExt};
 
-use std::path::Path;
-use std::{env, fs, io, process};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+use std::{env, io, process, thread};
 
 #[cfg(feature = "color")]
 use colored::Colorize;
 #[cfg(feature = "color")]
-use colored_json::to_colored_json_auto;
+use colored_json::{to_colored_json, ColorMode};
 
-mod binary;
+mod cache;
+mod csv;
+mod dedup;
+mod diff;
+mod fat;
+mod features;
+mod fields;
+mod grid;
+mod group;
+mod html;
+mod output;
+mod policy;
+#[cfg(feature = "remote")]
+mod remote;
+mod sarif;
+mod summary;
+mod table;
 
-use binary::{
-    BinSpecificProperties, BinType, Binaries, Binary, Process, Processes,
+use cache::Cache;
+use checksec::binary::{
+    Binaries, Binary, ParseError, Process, Processes, OUTPUT_VERSION,
+};
+use checksec::{
+    parse_bytes, parse_path_hashed, parse_path_with_limit, underline,
 };
-#[cfg(feature = "elf")]
-use checksec::elf;
-#[cfg(feature = "macho")]
-use checksec::macho;
-#[cfg(feature = "pe")]
-use checksec::pe;
-use checksec::underline;
+use output::{out, outln};
 
 fn json_print(data: &Value, pretty: bool) {
     if pretty {
         #[cfg(feature = "color")]
-        if let Ok(colored_json) = to_colored_json_auto(data) {
-            println!("{}", colored_json);
+        if let Ok(colored_json) = to_colored_json(
+            data,
+            if colored::control::SHOULD_COLORIZE.should_colorize() {
+                ColorMode::On
+            } else {
+                ColorMode::Off
+            },
+        ) {
+            outln!("{}", colored_json);
         }
         #[cfg(not(feature = "color"))]
         if let Ok(json_str) = to_string_pretty(data) {
-            println!("{}", json_str);
+            outln!("{}", json_str);
         }
     } else {
-        println!("{}", data);
+        outln!("{}", data);
     }
 }
 
-fn parse(file: &Path) -> Result<Vec<Binary>, Error> {
-    let fp = fs::File::open(file);
-    if let Err(err) = fp {
-        return Err(Error::IO(err));
-    }
-    if let Ok(buffer) = unsafe { Mmap::map(&fp.unwrap()) } {
-        match Object::parse(&buffer)? {
-            #[cfg(feature = "elf")]
-            Object::Elf(elf) => {
-                let results = elf::CheckSecResults::parse(&elf);
-                let bin_type =
-                    if elf.is_64 { BinType::Elf64 } else { BinType::Elf32 };
-                return Ok(vec![Binary::new(
-                    bin_type,
-                    file.display().to_string(),
-                    BinSpecificProperties::Elf(results),
-                )]);
-            }
-            #[cfg(feature = "pe")]
-            Object::PE(pe) => {
-                let results = pe::CheckSecResults::parse(&pe, &buffer);
-                let bin_type =
-                    if pe.is_64 { BinType::PE64 } else { BinType::PE32 };
-                return Ok(vec![Binary::new(
-                    bin_type,
-                    file.display().to_string(),
-                    BinSpecificProperties::PE(results),
-                )]);
+/// print one json object per binary and flush right away, so results of
+/// long scans can be consumed while scanning
+///
+/// with `insecure_only` nothing is printed if every binary (every slice of
+/// a fat `MachO`) is fully hardened, `selected` restricts the printed
+/// fields and `collapse_fat` prints all slices of a fat `MachO` as one
+/// object
+fn ndjson_print(
+    bins: &[Binary],
+    insecure_only: bool,
+    selected: Option<&[String]>,
+    collapse_fat: bool,
+) {
+    if insecure_only && bins.iter().all(Binary::is_fully_hardened) {
+        return;
+    }
+    let lines: Vec<Value> = match selected {
+        Some(selected) => {
+            bins.iter().map(|bin| fields::project(bin, selected)).collect()
+        }
+        None if collapse_fat => {
+            fat::collapse(bins).iter().map(|entry| json!(entry)).collect()
+        }
+        None => bins.iter().map(|bin| json!(bin)).collect(),
+    };
+    for line in lines {
+        if output::write(format_args!("{}\n", line)).is_err() {
+            return;
+        }
+    }
+}
+
+/// print `data` as a yaml document, sharing the layout of the json output
+fn yaml_print<T: Serialize>(data: &T) {
+    match serde_yaml::to_string(data) {
+        Ok(yaml) => out!("{}", yaml),
+        Err(msg) => eprintln!("Can not serialize results to yaml: {}", msg),
+    }
+}
+
+/// read a whole binary from stdin, reported as `<stdin>`
+fn parse_stdin() -> Result<Vec<Binary>, ParseError> {
+    let mut buffer: Vec<u8> = Vec::new();
+    io::stdin().read_to_end(&mut buffer)?;
+    parse_bytes("<stdin>", &buffer)
+}
+
+/// whether `file` is an `http://` or `https://` URL rather than a path
+fn is_url(file: &str) -> bool {
+    file.starts_with("http://") || file.starts_with("https://")
+}
+
+/// download `url` into memory and parse it, see [`remote::fetch`]
+#[cfg(feature = "remote")]
+use remote::fetch as parse_url;
+
+#[cfg(not(feature = "remote"))]
+fn parse_url(
+    _url: &str,
+    _max_file_size: Option<u64>,
+) -> Result<Vec<Binary>, ParseError> {
+    Err(io::Error::other("checksec was built without the remote feature")
+        .into())
+}
+
+/// expand `-f` glob patterns into the matching files, exiting when a
+/// pattern is invalid or matches no file
+fn expand_globs<'a>(patterns: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for pattern in patterns {
+        if pattern == "-" {
+            files.push(pattern.to_string());
+            continue;
+        }
+        let paths = match glob::glob(pattern) {
+            Ok(paths) => paths,
+            Err(msg) => {
+                eprintln!("Invalid glob pattern {}: {}", pattern, msg);
+                process::exit(1);
             }
-            #[cfg(feature = "macho")]
-            Object::Mach(mach) => match mach {
-                Mach::Binary(macho) => {
-                    let results = macho::CheckSecResults::parse(&macho);
-                    let bin_type = if macho.is_64 {
-                        BinType::MachO64
-                    } else {
-                        BinType::MachO32
-                    };
-                    return Ok(vec![Binary::new(
-                        bin_type,
-                        file.display().to_string(),
-                        BinSpecificProperties::MachO(results),
-                    )]);
+        };
+        let before = files.len();
+        files.extend(
+            paths
+                .flatten()
+                .filter(|path| path.is_file())
+                .map(|path| path.display().to_string()),
+        );
+        if files.len() == before {
+            eprintln!("No files matched {}", underline!(pattern));
+            process::exit(1);
+        }
+    }
+    files
+}
+
+/// patterns of a `--include-from`/`--exclude-from` file, one per line,
+/// exiting when the file can not be read
+fn read_patterns(file: &str) -> Vec<String> {
+    match fs::read_to_string(file) {
+        Ok(contents) => contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect(),
+        Err(err) => {
+            eprintln!("Can not read {}: {}", underline!(file), err);
+            process::exit(1);
+        }
+    }
+}
+
+/// gitignore style filters of the directory walk, anchored patterns are
+/// relative to `root`. Excluded paths win over included ones, and with
+/// an include file only the files it lists are checked.
+fn path_filters(
+    root: &Path,
+    include: Option<&str>,
+    exclude: Option<&str>,
+) -> Override {
+    let mut builder = OverrideBuilder::new(root);
+    let included = include
+        .map(read_patterns)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|pattern| (pattern.clone(), pattern));
+    // overrides are allowlists, `!` turns a pattern into an exclusion
+    let excluded = exclude
+        .map(read_patterns)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|pattern| (format!("!{}", pattern), pattern));
+    for (glob, pattern) in included.chain(excluded) {
+        if let Err(msg) = builder.add(&glob) {
+            eprintln!("Invalid path pattern {}: {}", pattern, msg);
+            process::exit(1);
+        }
+    }
+    builder.build().unwrap_or_else(|msg| {
+        eprintln!("Invalid path patterns: {}", msg);
+        process::exit(1);
+    })
+}
+
+/// main executable of a running process
+///
+/// on Linux this is `/proc/<pid>/exe`, which stays readable even when the
+/// file on disk was deleted or replaced since the process started
+#[cfg(target_os = "linux")]
+fn process_exe(pid: Pid, _process: &sysinfo::Process) -> PathBuf {
+    PathBuf::from(format!("/proc/{}/exe", pid))
+}
+#[cfg(not(target_os = "linux"))]
+fn process_exe(_pid: Pid, process: &sysinfo::Process) -> PathBuf {
+    process.exe().to_path_buf()
+}
+
+/// keep only the `arch` slices of fat `MachO` binaries, single
+/// architecture binaries are always kept
+fn select_arch(
+    file: &str,
+    bins: Vec<Binary>,
+    arch: Option<&str>,
+) -> Vec<Binary> {
+    let arch = match arch {
+        Some(arch) if bins.iter().any(|bin| bin.arch.is_some()) => arch,
+        _ => return bins,
+    };
+    let available: Vec<String> =
+        bins.iter().filter_map(|bin| bin.arch.clone()).collect();
+    let selected: Vec<Binary> = bins
+        .into_iter()
+        .filter(|bin| bin.arch.as_deref() == Some(arch))
+        .collect();
+    if selected.is_empty() {
+        eprintln!(
+            "No {} architecture in fat binary {} [available: {}]",
+            arch,
+            underline!(file),
+            available.join(", ")
+        );
+    }
+    selected
+}
+
+/// output format for scan results
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Ndjson,
+    Csv,
+    Sarif,
+    Table,
+    /// one line of single character flags per binary
+    Grid,
+    Yaml,
+    /// no per-binary output, for `--quiet` and `--stats-only`
+    Quiet,
+}
+
+/// how directories are traversed
+struct WalkOptions {
+    /// follow symbolic links, reporting files reachable through several
+    /// links only once
+    follow_symlinks: bool,
+    /// also scan hidden files and files matched by `.gitignore`, `.ignore`
+    /// and similar filters
+    no_ignore: bool,
+    /// descend at most this many directories, `1` only scans the contents
+    /// of the given directories
+    max_depth: Option<usize>,
+    /// largest file read into memory when it can not be memory mapped
+    max_file_size: Option<u64>,
+    /// report files with the same contents once
+    dedup: bool,
+    /// show a progress bar on stderr while scanning
+    progress: bool,
+    /// time budget for parsing a single file
+    timeout: Option<Duration>,
+    /// only check files with one of these lowercase extensions, all files
+    /// when empty
+    include_ext: Vec<String>,
+    /// skip files with one of these lowercase extensions
+    exclude_ext: Vec<String>,
+    /// `--include-from`/`--exclude-from` patterns
+    overrides: Override,
+}
+impl WalkOptions {
+    fn builder(&self, basepaths: &[&Path]) -> WalkBuilder {
+        let mut builder = WalkBuilder::new(basepaths[0]);
+        for basepath in &basepaths[1..] {
+            builder.add(basepath);
+        }
+        // loops are detected and reported as walk errors by `ignore`
+        builder.follow_links(self.follow_symlinks);
+        builder.standard_filters(!self.no_ignore);
+        builder.overrides(self.overrides.clone());
+        builder.max_depth(self.max_depth);
+        builder
+    }
+    /// whether `path` passes the `--include-ext` and `--exclude-ext`
+    /// filters
+    fn wanted(&self, path: &Path) -> bool {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let matches = |extensions: &[String]| {
+            extensions.iter().any(|ext| has_extension(&name, ext))
+        };
+        (self.include_ext.is_empty() || matches(&self.include_ext))
+            && !matches(&self.exclude_ext)
+    }
+}
+
+/// whether the file `name` ends with `.ext`, or has it before a version
+/// suffix such as `libc.so.6` for `so`
+fn has_extension(name: &str, ext: &str) -> bool {
+    let suffix = format!(".{}", ext);
+    match name.rfind(&suffix) {
+        Some(idx) => {
+            let rest = &name[idx + suffix.len()..];
+            rest.is_empty()
+                || rest.starts_with('.')
+                    && rest[1..]
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || c == '.')
+        }
+        None => false,
+    }
+}
+
+/// split a comma separated `--include-ext`/`--exclude-ext` list into
+/// lowercase extensions without the leading dot
+fn parse_extensions(list: Option<&str>) -> Vec<String> {
+    list.unwrap_or_default()
+        .split(',')
+        .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn walk(
+    basepaths: &[&Path],
+    options: &WalkOptions,
+    format: Format,
+    pretty: bool,
+    verbose: bool,
+    insecure_only: bool,
+    group_by: bool,
+    collapse_fat: bool,
+    selected: Option<&[String]>,
+    ignore_errors: bool,
+    arch: Option<&str>,
+    cache: Option<&Cache>,
+) -> Vec<Binary> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    for entry in options.builder(basepaths).build() {
+        match entry {
+            Ok(entry) if entry.file_type().is_some_and(|ft| ft.is_file()) => {
+                if !options.wanted(entry.path()) {
+                    continue;
                 }
-                Mach::Fat(fatmach) => {
-                    let mut fat_bins: Vec<Binary> = Vec::new();
-                    for (idx, _) in fatmach.iter_arches().enumerate() {
-                        let container: MachO = fatmach.get(idx).unwrap();
-                        let results =
-                            macho::CheckSecResults::parse(&container);
-                        let bin_type = if container.is_64 {
-                            BinType::MachO64
-                        } else {
-                            BinType::MachO32
-                        };
-                        fat_bins.append(&mut vec![Binary::new(
-                            bin_type,
-                            file.display().to_string(),
-                            BinSpecificProperties::MachO(results),
-                        )]);
+                if options.follow_symlinks {
+                    let real = entry
+                        .path()
+                        .canonicalize()
+                        .unwrap_or_else(|_| entry.path().to_path_buf());
+                    if !seen.insert(real) {
+                        continue;
                     }
-                    return Ok(fat_bins);
                 }
-            },
-            _ => return Err(Error::BadMagic(0)),
+                files.push(entry.into_path());
+            }
+            Ok(_) => (),
+            Err(err) if !ignore_errors => {
+                eprintln!("Can not walk directory: {}", err);
+            }
+            Err(_) => (),
+        }
+    }
+    let progress = if options.progress {
+        progress_bar(files.len() as u64)
+    } else {
+        ProgressBar::hidden()
+    };
+    let results: Vec<(&PathBuf, Result<Vec<Binary>, ParseError>)> = files
+        .par_iter()
+        .map(|file| {
+            progress.set_message(file.display().to_string());
+            let name = file.display().to_string();
+            let result = parse_cached(
+                cache,
+                file,
+                options.max_file_size,
+                options.dedup,
+                options.timeout,
+            );
+            let result = match result {
+                Ok(results) => Ok(select_arch(&name, results, arch)),
+                Err(ParseError::FatArch { binaries, errors }) => {
+                    Err(ParseError::FatArch {
+                        binaries: select_arch(&name, binaries, arch),
+                        errors,
+                    })
+                }
+                Err(msg) => Err(msg),
+            };
+            if format == Format::Ndjson {
+                if let Ok(bins)
+                | Err(ParseError::FatArch { binaries: bins, .. }) = &result
+                {
+                    progress.suspend(|| {
+                        ndjson_print(
+                            bins,
+                            insecure_only,
+                            selected,
+                            collapse_fat,
+                        );
+                    });
+                }
+            }
+            progress.inc(1);
+            (file, result)
+        })
+        .collect();
+    progress.finish_and_clear();
+    let mut scanned: Vec<(String, Vec<Binary>)> = Vec::new();
+    for (file, result) in results {
+        let name = file.display().to_string();
+        match result {
+            Ok(results) => scanned.push((name, results)),
+            Err(ParseError::FatArch { binaries, errors }) => {
+                scanned.push((name.clone(), binaries));
+                if !ignore_errors {
+                    eprintln!(
+                        "Can not parse binary file {}: {}",
+                        underline!(name),
+                        ParseError::FatArch { binaries: Vec::new(), errors }
+                    );
+                }
+            }
+            // directories are expected to contain files of other formats
+            // and empty placeholders
+            Err(
+                ParseError::Empty
+                | ParseError::TooSmall(_)
+                | ParseError::Goblin(Error::BadMagic(_)),
+            ) => (),
+            Err(msg) if !ignore_errors => eprintln!(
+                "Can not parse binary file {}: {}",
+                underline!(file.display().to_string()),
+                msg
+            ),
+            Err(_) => (),
+        }
+    }
+    // keep output stable across runs regardless of scheduling, so the
+    // first path in sort order is the one reported for duplicates
+    scanned.sort_by(|a, b| a.0.cmp(&b.0));
+    let bins: Vec<Binary> = if options.dedup {
+        dedup::dedup(scanned)
+    } else {
+        scanned.into_iter().flat_map(|(_, binaries)| binaries).collect()
+    };
+    if format == Format::Ndjson {
+        // already printed while scanning
+        return bins;
+    }
+    print_binaries(
+        bins,
+        format,
+        pretty,
+        verbose,
+        insecure_only,
+        group_by,
+        collapse_fat,
+        selected,
+    )
+}
+
+/// progress bar with the number of scanned files and the current path,
+/// drawn on stderr only when it is a terminal
+fn progress_bar(files: u64) -> ProgressBar {
+    let progress = ProgressBar::with_draw_target(
+        Some(files),
+        ProgressDrawTarget::stderr(),
+    );
+    if let Ok(style) = ProgressStyle::with_template(
+        "{spinner} [{elapsed_precise}] {pos}/{len} files {wide_msg}",
+    ) {
+        progress.set_style(style);
+    }
+    progress
+}
+
+/// parse `file`, hashing its contents for `--dedup` when `hashed` is set
+///
+/// with a `timeout` the file is parsed on its own thread and abandoned once
+/// the time budget is exceeded, the abandoned thread can not be stopped
+/// and keeps running in the background until it finishes or checksec
+/// exits
+fn parse_file(
+    file: &Path,
+    max_file_size: Option<u64>,
+    hashed: bool,
+    timeout: Option<Duration>,
+) -> Result<Vec<Binary>, ParseError> {
+    let parse = move |file: &Path| {
+        if hashed {
+            parse_path_hashed(file, max_file_size)
+        } else {
+            parse_path_with_limit(file, max_file_size)
+        }
+    };
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return parse(file),
+    };
+    let (sender, receiver) = mpsc::channel();
+    let path = file.to_path_buf();
+    if thread::Builder::new()
+        .spawn(move || sender.send(parse(&path)).ok())
+        .is_err()
+    {
+        // out of threads, parse without a time budget instead
+        return parse(file);
+    }
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        #[allow(clippy::cast_possible_truncation)]
+        Err(RecvTimeoutError::Timeout) => {
+            Err(ParseError::TimedOut(timeout.as_millis() as u64))
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(ParseError::Goblin(
+            Error::Malformed("parser panicked".to_string()),
+        )),
+    }
+}
+
+/// [`parse_file`] served from `cache` while `file` is unchanged, only
+/// successfully parsed files are stored
+fn parse_cached(
+    cache: Option<&Cache>,
+    file: &Path,
+    max_file_size: Option<u64>,
+    hashed: bool,
+    timeout: Option<Duration>,
+) -> Result<Vec<Binary>, ParseError> {
+    let cache = match cache {
+        Some(cache) => cache,
+        None => return parse_file(file, max_file_size, hashed, timeout),
+    };
+    if let Some(binaries) = cache.get(file, hashed) {
+        return Ok(binaries);
+    }
+    let result = parse_file(file, max_file_size, hashed, timeout);
+    if let Ok(binaries) = &result {
+        cache.insert(file, hashed, binaries);
+    }
+    result
+}
+
+/// parse a size in bytes with an optional `K`, `M` or `G` *(1024 based)*
+/// suffix
+fn parse_size(size: &str) -> Result<u64, String> {
+    let (digits, unit) = match size.char_indices().last() {
+        Some((idx, 'K' | 'k')) => (&size[..idx], 1 << 10),
+        Some((idx, 'M' | 'm')) => (&size[..idx], 1 << 20),
+        Some((idx, 'G' | 'g')) => (&size[..idx], 1 << 30),
+        _ => (size, 1),
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|digits| digits.checked_mul(unit))
+        .ok_or_else(|| format!("invalid size: {}", size))
+}
+
+/// human readable output of a single binary, `verbose` adds the evidence
+/// behind the results
+fn text(bin: &Binary, verbose: bool) -> String {
+    if verbose {
+        format!("{:#}", bin)
+    } else {
+        bin.to_string()
+    }
+}
+
+/// drop the entropy of every section, it is only printed by `--verbose`
+/// or when `selected` with `--fields`
+fn strip_section_entropy(
+    bins: &mut [Binary],
+    verbose: bool,
+    selected: Option<&[String]>,
+) {
+    let shown = selected.is_some_and(|selected| {
+        selected.iter().any(|field| field == "section_entropy")
+    });
+    if !verbose && !shown {
+        for bin in bins {
+            bin.strip_section_entropy();
         }
     }
-    Err(Error::IO(io::Error::last_os_error()))
 }
 
-fn walk(basepath: &Path, json: bool, pretty: bool) {
-    let mut bins: Vec<Binary> = Vec::new();
-    for result in Walk::new(basepath).flatten() {
-        if let Some(filetype) = result.file_type() {
-            if filetype.is_file() {
-                if let Ok(mut result) = parse(result.path()) {
-                    if json {
-                        bins.append(&mut result);
+/// human readable output of a single binary, restricted to the `selected`
+/// fields when given
+fn line(bin: &Binary, verbose: bool, selected: Option<&[String]>) -> String {
+    match selected {
+        Some(selected) => fields::text(bin, selected),
+        None => text(bin, verbose),
+    }
+}
+
+/// print binaries sectioned by format, with a header per group in text
+/// output and a `groups` list in json/yaml output
+fn print_groups(
+    groups: group::Groups,
+    format: Format,
+    pretty: bool,
+    verbose: bool,
+    selected: Option<&[String]>,
+) -> Vec<Binary> {
+    match format {
+        Format::Text => {
+            for (idx, group) in groups.groups.iter().enumerate() {
+                if idx > 0 {
+                    outln!();
+                }
+                let header = format!(
+                    "{} ({} binaries)",
+                    group.format,
+                    group.binaries.len()
+                );
+                #[cfg(feature = "color")]
+                let header = header.bold();
+                outln!("{}", header);
+                for bin in &group.binaries {
+                    outln!("{}", line(bin, verbose, selected));
+                }
+            }
+        }
+        Format::Json => json_print(&groups.project(selected), pretty),
+        Format::Yaml => yaml_print(&groups.project(selected)),
+        // flat formats only keep the grouped order
+        _ => {
+            return print_binaries(
+                groups.into_binaries(),
+                format,
+                pretty,
+                verbose,
+                false,
+                false,
+                false,
+                selected,
+            )
+        }
+    }
+    groups.into_binaries()
+}
+
+/// print scanned binaries, handing them back for policy evaluation
+///
+/// with `insecure_only` files where every binary (every slice of a fat
+/// `MachO`) is fully hardened are left out of the output, with `group_by`
+/// binaries are grouped by format and sorted by path, with `collapse_fat`
+/// the slices of fat `MachO` binaries are merged in text, json, ndjson and
+/// yaml output and `selected` restricts the output to these fields
+#[allow(clippy::too_many_arguments)]
+fn print_binaries(
+    mut bins: Vec<Binary>,
+    format: Format,
+    pretty: bool,
+    verbose: bool,
+    insecure_only: bool,
+    group_by: bool,
+    collapse_fat: bool,
+    selected: Option<&[String]>,
+) -> Vec<Binary> {
+    strip_section_entropy(&mut bins, verbose, selected);
+    if insecure_only {
+        let insecure: HashSet<String> = bins
+            .iter()
+            .filter(|bin| !bin.is_fully_hardened())
+            .map(|bin| bin.file.clone())
+            .collect();
+        let (shown, mut hidden): (Vec<Binary>, Vec<Binary>) =
+            bins.into_iter().partition(|bin| insecure.contains(&bin.file));
+        let mut bins = print_binaries(
+            shown,
+            format,
+            pretty,
+            verbose,
+            false,
+            group_by,
+            collapse_fat,
+            selected,
+        );
+        bins.append(&mut hidden);
+        return bins;
+    }
+    if group_by {
+        return print_groups(
+            group::by_type(bins),
+            format,
+            pretty,
+            verbose,
+            selected,
+        );
+    }
+    if let Some(selected) = selected {
+        match format {
+            Format::Text => {
+                for bin in &bins {
+                    outln!("{}", fields::text(bin, selected));
+                }
+            }
+            Format::Json => {
+                json_print(&fields::project_all(&bins, selected), pretty);
+            }
+            Format::Ndjson => {
+                ndjson_print(&bins, false, Some(selected), false);
+            }
+            Format::Csv => csv::print_fields(&bins, selected),
+            Format::Table => table::print_fields(&bins, selected),
+            // `--grid` conflicts with `--fields`
+            Format::Grid => grid::print(&bins),
+            Format::Yaml => yaml_print(&fields::project_all(&bins, selected)),
+            // every result refers to a mitigation rule
+            Format::Sarif => json_print(&sarif::log(&bins), pretty),
+            Format::Quiet => (),
+        }
+        return bins;
+    }
+    if collapse_fat
+        && matches!(format, Format::Text | Format::Json | Format::Yaml)
+    {
+        let entries = fat::collapse(&bins);
+        let collapsed =
+            json!({ "binaries": entries, "version": OUTPUT_VERSION });
+        match format {
+            Format::Text => {
+                for entry in &entries {
+                    if verbose {
+                        outln!("{:#}", entry);
                     } else {
-                        for bin in &result {
-                            println!("{}", bin);
-                        }
+                        outln!("{}", entry);
                     }
                 }
             }
+            Format::Json => json_print(&collapsed, pretty),
+            _ => yaml_print(&collapsed),
         }
+        drop(entries);
+        return bins;
     }
-    if json {
-        json_print(&json!(Binaries::new(bins)), pretty);
+    match format {
+        Format::Text => {
+            for bin in &bins {
+                outln!("{}", text(bin, verbose));
+            }
+            bins
+        }
+        Format::Json => {
+            let bins = Binaries::new(bins);
+            json_print(&json!(bins), pretty);
+            bins.binaries
+        }
+        Format::Ndjson => {
+            ndjson_print(&bins, false, None, collapse_fat);
+            bins
+        }
+        Format::Csv => {
+            csv::print(&bins);
+            bins
+        }
+        Format::Sarif => {
+            json_print(&sarif::log(&bins), pretty);
+            bins
+        }
+        Format::Table => {
+            table::print(&bins);
+            bins
+        }
+        Format::Grid => {
+            grid::print(&bins);
+            bins
+        }
+        Format::Yaml => {
+            let bins = Binaries::new(bins);
+            yaml_print(&bins);
+            bins.binaries
+        }
+        Format::Quiet => bins,
     }
 }
+
+/// report binaries missing any of the `required` mitigations on stderr,
+/// returning whether all binaries passed
+fn enforce(bins: &[Binary], required: &[&str]) -> bool {
+    let mut passed = true;
+    for bin in bins {
+        let missing = policy::violations(bin, required);
+        if !missing.is_empty() {
+            passed = false;
+            eprintln!(
+                "{} ({}) is missing {}",
+                underline!(bin.file.clone()),
+                bin.binarytype,
+                missing.join(", ")
+            );
+        }
+    }
+    passed
+}
+
+/// report binaries violating `policy` on stderr, returning whether all
+/// binaries passed
+fn enforce_policy(bins: &[Binary], policy: &policy::Policy) -> bool {
+    let mut passed = true;
+    for bin in bins {
+        let violations = policy.violations(bin);
+        if !violations.is_empty() {
+            passed = false;
+            eprintln!(
+                "{} ({}) violates the policy, missing {}",
+                underline!(bin.file.clone()),
+                bin.binarytype,
+                violations.join(", ")
+            );
+        }
+    }
+    passed
+}
 #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
 fn main() {
-    let args = App::new("checksec")
+    let app = App::new("checksec")
         .about(crate_description!())
         .author(crate_authors!())
         .version(crate_version!())
+        // `checksec features` does not need a target
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .subcommand(
+            SubCommand::with_name("features")
+                .about(
+                    "Print the optional cargo features and the goblin \
+                     version this build was compiled with",
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .help("Output in json format"),
+                ),
+        )
+        .arg(
+            Arg::with_name("arch")
+                .long("arch")
+                .value_name("ARCH")
+                .help(
+                    "Only check this architecture of fat MachO binaries \
+                     [e.g. x86_64, arm64]",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("fail-on")
+                .long("fail-on")
+                .value_name("MITIGATIONS")
+                .help(
+                    "Exit with a non-zero status if any scanned binary is \
+                     missing one of these mitigations [comma separated, \
+                     e.g. nx,pie,relro]",
+                )
+                .takes_value(true)
+                .validator(|list| policy::parse_required(&list).map(|_| ())),
+        )
+        .arg(
+            Arg::with_name("policy")
+                .long("policy")
+                .value_name("FILE")
+                .help(
+                    "Exit with a non-zero status if any scanned binary \
+                     violates the mitigations required for its format by \
+                     this yaml policy file",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("fields")
+                .long("fields")
+                .value_name("FIELDS")
+                .help(
+                    "Only output these fields of file and directory scan \
+                     results [comma separated, e.g. file,nx,pie,relro]",
+                )
+                .takes_value(true)
+                .validator(|list| fields::parse(&list).map(|_| ()))
+                .conflicts_with_all(&[
+                    "diff",
+                    "pid",
+                    "process",
+                    "process-all",
+                    "sarif",
+                    "verbose",
+                ]),
+        )
         .arg(
             Arg::with_name("file")
                 .short("f")
                 .long("file")
                 .value_name("FILE")
-                .help("Target file")
+                .help(
+                    "Target file [can be specified multiple times, - reads \
+                     from stdin, http(s) URLs are downloaded with the remote \
+                     feature]",
+                )
+                .takes_value(true)
+                .multiple(true),
+        )
+        .arg(
+            Arg::with_name("csv")
+                .long("csv")
+                .help("Output in csv format")
+                .conflicts_with("json"),
+        )
+        .arg(
+            Arg::with_name("diff")
+                .long("diff")
+                .value_names(&["OLD", "NEW"])
+                .help(
+                    "Print mitigations that differ between two builds of a \
+                     binary, exiting with a non-zero status if any got \
+                     weaker",
+                )
                 .takes_value(true)
-                .conflicts_with("directory")
-                .conflicts_with("pid")
-                .conflicts_with("process")
-                .conflicts_with("process-all"),
+                .number_of_values(2),
+        )
+        .arg(
+            Arg::with_name("cache")
+                .long("cache")
+                .value_name("FILE")
+                .help(
+                    "Keep the results in FILE and reuse them for files \
+                     whose size and modification time did not change \
+                     [created when missing]",
+                )
+                .takes_value(true)
+                .conflicts_with_all(&[
+                    "diff",
+                    "pid",
+                    "process",
+                    "process-all",
+                ]),
+        )
+        .arg(
+            Arg::with_name("dedup")
+                .long("dedup")
+                .help(
+                    "Check files with the same contents once, listing the \
+                     other paths as duplicates [files are compared by \
+                     sha256]",
+                )
+                .conflicts_with_all(&[
+                    "diff",
+                    "ndjson",
+                    "pid",
+                    "process",
+                    "process-all",
+                ]),
         )
         .arg(
             Arg::with_name("directory")
                 .short("d")
                 .long("directory")
                 .value_name("DIRECTORY")
-                .help("Target directory")
+                .help("Target directory [can be specified multiple times]")
+                .takes_value(true)
+                .multiple(true),
+        )
+        .arg(
+            Arg::with_name("exclude-ext")
+                .long("exclude-ext")
+                .value_name("EXTS")
+                .help(
+                    "Skip files with one of these comma separated \
+                     extensions while scanning directories, e.g. debug,txt \
+                     [case insensitive]",
+                )
+                .takes_value(true)
+                .requires("directory"),
+        )
+        .arg(
+            Arg::with_name("exclude-from")
+                .long("exclude-from")
+                .value_name("FILE")
+                .help(
+                    "Skip paths matching the gitignore style patterns in \
+                     FILE while scanning directories, one per line \
+                     [anchored patterns are relative to the first \
+                     directory]",
+                )
                 .takes_value(true)
-                .conflicts_with("file")
-                .conflicts_with("pid")
-                .conflicts_with("process")
-                .conflicts_with("process-all"),
+                .requires("directory"),
+        )
+        .arg(Arg::with_name("follow-symlinks").long("follow-symlinks").help(
+            "Follow symbolic links while scanning directories, \
+                     files reached through several links are checked once \
+                     [default: links are skipped]",
+        ))
+        .arg(
+            Arg::with_name("glob")
+                .long("glob")
+                .help(
+                    "Expand --file arguments as glob patterns [e.g. \
+                     '/usr/bin/python*']",
+                )
+                .requires("file"),
+        )
+        .arg(
+            Arg::with_name("collapse-fat")
+                .long("collapse-fat")
+                .help(
+                    "Report all architectures of a fat MachO binary as one \
+                     entry with the results keyed by architecture [text, \
+                     json, ndjson and yaml output]",
+                )
+                .conflicts_with_all(&[
+                    "diff",
+                    "fields",
+                    "group-by",
+                    "pid",
+                    "process",
+                    "process-all",
+                ]),
+        )
+        .arg(
+            Arg::with_name("grid")
+                .long("grid")
+                .help(
+                    "Output one line of single character flags per binary \
+                     after a legend [UPPER enabled, lower partial, - \
+                     disabled, . not applicable]",
+                )
+                .conflicts_with_all(&[
+                    "csv", "fields", "json", "ndjson", "sarif", "table",
+                    "yaml",
+                ]),
+        )
+        .arg(
+            Arg::with_name("group-by")
+                .long("group-by")
+                .value_name("KEY")
+                .help(
+                    "Group file and directory scan results by binary format, \
+                     sorted by path within each group [sections the text, \
+                     json and yaml output]",
+                )
+                .takes_value(true)
+                .possible_values(&["type"])
+                .conflicts_with_all(&[
+                    "diff",
+                    "ndjson",
+                    "pid",
+                    "process",
+                    "process-all",
+                ]),
+        )
+        .arg(
+            Arg::with_name("ignore-errors")
+                .long("ignore-errors")
+                .help("Do not report unreadable files while scanning"),
+        )
+        .arg(
+            Arg::with_name("include-ext")
+                .long("include-ext")
+                .value_name("EXTS")
+                .help(
+                    "Only check files with one of these comma separated \
+                     extensions while scanning directories, e.g. \
+                     so,dll,dylib [case insensitive, versioned names such \
+                     as libc.so.6 match so]",
+                )
+                .takes_value(true)
+                .requires("directory"),
+        )
+        .arg(
+            Arg::with_name("include-from")
+                .long("include-from")
+                .value_name("FILE")
+                .help(
+                    "Only check files matching the gitignore style patterns \
+                     in FILE while scanning directories, one per line \
+                     [--exclude-from wins when both match]",
+                )
+                .takes_value(true)
+                .requires("directory"),
+        )
+        .arg(
+            Arg::with_name("insecure-only")
+                .long("insecure-only")
+                .help("Only output binaries missing a baseline mitigation"),
+        )
+        .arg(
+            Arg::with_name("jobs")
+                .long("jobs")
+                .value_name("N")
+                .help(
+                    "Number of threads used to scan directories [default: \
+                     number of logical CPUs]",
+                )
+                .takes_value(true)
+                .validator(|n| match n.parse::<usize>() {
+                    Ok(n) if n > 0 => Ok(()),
+                    _ => Err(format!("invalid number of jobs: {}", n)),
+                }),
+        )
+        .arg(
+            Arg::with_name("html")
+                .long("html")
+                .value_name("FILE")
+                .help(
+                    "Also write a self-contained html report with a summary \
+                     and a sortable table of the scanned binaries to FILE",
+                )
+                .takes_value(true),
         )
         .arg(
             Arg::with_name("json")
@@ -179,89 +1162,417 @@ fn main() {
                 .long("json")
                 .help("Output in json format"),
         )
+        .arg(
+            Arg::with_name("max-depth")
+                .long("max-depth")
+                .value_name("N")
+                .help(
+                    "Maximum directory depth to scan [1 only checks the \
+                     contents of each directory]",
+                )
+                .takes_value(true)
+                .validator(|n| match n.parse::<usize>() {
+                    Ok(n) if n > 0 => Ok(()),
+                    _ => Err(format!("invalid depth: {}", n)),
+                }),
+        )
+        .arg(
+            Arg::with_name("max-file-size")
+                .long("max-file-size")
+                .value_name("SIZE")
+                .help(
+                    "Skip files that can not be memory mapped and are larger \
+                     than SIZE bytes instead of reading them into memory \
+                     [K, M and G suffixes are accepted, e.g. 512M]",
+                )
+                .takes_value(true)
+                .validator(|size| parse_size(&size).map(|_| ())),
+        )
+        .arg(
+            Arg::with_name("ndjson")
+                .long("ndjson")
+                .help(
+                    "Output one json object per binary as soon as it is \
+                     checked",
+                )
+                .conflicts_with_all(&["csv", "json", "sarif"]),
+        )
+        .arg(Arg::with_name("no-ignore").long("no-ignore").help(
+            "Scan hidden files and files excluded by .gitignore, .ignore \
+             and similar files [default: these are skipped]",
+        ))
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .value_name("FILE")
+                .help(
+                    "Write the results to FILE instead of stdout, progress \
+                     and errors stay on stderr [disables --color auto]",
+                )
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("pretty")
                 .long("pretty")
                 .help("Human readable json output")
-                .requires("json"),
+                .requires("json-output"),
         )
+        .arg(
+            Arg::with_name("progress")
+                .long("progress")
+                .help(
+                    "Show the number of scanned files and the current path \
+                     while scanning directories [on stderr, only when it \
+                     is a terminal]",
+                )
+                .requires("directory"),
+        )
+        .arg(
+            Arg::with_name("quiet")
+                .short("q")
+                .long("quiet")
+                .help(
+                    "Do not print the results of each binary, only errors, \
+                     --summary and --fail-on/--policy failures",
+                )
+                .conflicts_with_all(&[
+                    "csv", "diff", "fields", "grid", "json", "ndjson",
+                    "sarif", "table", "verbose", "yaml",
+                ]),
+        )
+        .arg(
+            Arg::with_name("sarif")
+                .long("sarif")
+                .help("Output in SARIF format for code scanning tools")
+                .conflicts_with("csv"),
+        )
+        .arg(Arg::with_name("print-schema").long("print-schema").help(
+            "Print the JSON Schema of the json output of file and \
+                     directory scans and exit",
+        ))
         .arg(
             Arg::with_name("process")
                 .short("p")
                 .long("process")
                 .value_name("NAME")
                 .help("Name of running process to check")
-                .takes_value(true)
-                .conflicts_with("directory")
-                .conflicts_with("file")
-                .conflicts_with("pid")
-                .conflicts_with("process-all"),
+                .takes_value(true),
         )
         .arg(
             Arg::with_name("pid")
                 .long("pid")
                 .value_name("PID")
                 .help(
-                    "Process ID of running process to check [multiple IDs
-                       can be specified separated by a comma]",
+                    "Process ID of running process to check [multiple IDs \
+                     can be specified separated by a comma]",
                 )
-                .takes_value(true)
-                .conflicts_with("directory")
-                .conflicts_with("file")
-                .conflicts_with("process")
-                .conflicts_with("process-all"),
+                .takes_value(true),
         )
         .arg(
             Arg::with_name("process-all")
                 .short("P")
                 .long("process-all")
-                .help("Check all running processes")
-                .conflicts_with("directory")
-                .conflicts_with("file")
-                .conflicts_with("pid")
-                .conflicts_with("process"),
+                .alias("proc-all")
+                .help(
+                    "Check all running processes [each executable is only \
+                     checked once]",
+                ),
+        )
+        .arg(Arg::with_name("summary").long("summary").help(
+            "Print enabled/total counts per mitigation and binary \
+                     format after the results [on stderr for machine \
+                     readable formats]",
+        ))
+        .arg(
+            Arg::with_name("stats-only")
+                .long("stats-only")
+                .help(
+                    "Only print the --summary counts instead of the results \
+                     of each binary, errors and --fail-on/--policy failures \
+                     are still reported",
+                )
+                .conflicts_with_all(&[
+                    "csv", "diff", "fields", "grid", "json", "ndjson",
+                    "sarif", "table", "verbose", "yaml",
+                ]),
         )
-        .get_matches();
+        .arg(
+            Arg::with_name("table")
+                .long("table")
+                .help(
+                    "Output an aligned table with one row per binary \
+                     [file column fits $COLUMNS]",
+                )
+                .conflicts_with_all(&[
+                    "csv", "json", "ndjson", "sarif", "yaml",
+                ]),
+        )
+        .arg(
+            Arg::with_name("timeout")
+                .long("timeout")
+                .value_name("MS")
+                .help(
+                    "Give up on files of file and directory scans that take \
+                     longer than MS milliseconds to check, reporting them \
+                     as timed out",
+                )
+                .takes_value(true)
+                .validator(|ms| match ms.parse::<u64>() {
+                    Ok(ms) if ms > 0 => Ok(()),
+                    _ => Err(format!("invalid timeout: {}", ms)),
+                }),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .short("v")
+                .long("verbose")
+                .help(
+                    "Print the evidence behind each result below it \
+                     [e.g. segment addresses, symbol names, load command \
+                     offsets]",
+                )
+                .conflicts_with_all(&[
+                    "csv", "grid", "json", "ndjson", "sarif", "table", "yaml",
+                ]),
+        )
+        .arg(
+            Arg::with_name("yaml")
+                .long("yaml")
+                .help("Output in yaml format")
+                .conflicts_with_all(&["csv", "json", "ndjson", "sarif"]),
+        )
+        // also keeps --json and --sarif mutually exclusive
+        .group(ArgGroup::with_name("json-output").args(&["json", "sarif"]))
+        .group(
+            ArgGroup::with_name("target")
+                .args(&[
+                    "diff",
+                    "directory",
+                    "file",
+                    "pid",
+                    "print-schema",
+                    "process",
+                    "process-all",
+                ])
+                .required(true),
+        );
+    #[cfg(feature = "color")]
+    let app = app.arg(
+        Arg::with_name("color")
+            .long("color")
+            .value_name("WHEN")
+            .help("When to colorize output")
+            .takes_value(true)
+            .possible_values(&["auto", "always", "never"])
+            .default_value("auto"),
+    );
+    let args = app.get_matches();
+
+    if let Some(file) = args.value_of("output") {
+        if let Err(err) = output::to_file(file) {
+            eprintln!("Can not create {}: {}", underline!(file), err);
+            process::exit(1);
+        }
+    }
 
-    let json = args.is_present("json");
-    let file = args.value_of("file");
-    let directory = args.value_of("directory");
+    #[cfg(feature = "color")]
+    match args.value_of("color") {
+        Some("always") => colored::control::set_override(true),
+        Some("never") => colored::control::set_override(false),
+        // stdout may still be a terminal while writing to a file
+        _ if output::is_file() => colored::control::set_override(false),
+        // colored already disables itself when stdout is not a tty
+        _ => (),
+    }
+
+    if let Some(features) = args.subcommand_matches("features") {
+        let built = features::Features::new();
+        if features.is_present("json") {
+            json_print(&json!(built), true);
+        } else {
+            out!("{}", built);
+        }
+        return;
+    }
+
+    if args.is_present("print-schema") {
+        json_print(&json!(schema_for!(Binaries)), true);
+        return;
+    }
+
+    let required = args
+        .value_of("fail-on")
+        .map(|list| policy::parse_required(list).unwrap_or_default());
+    let policy = args.value_of("policy").map(|file| {
+        policy::Policy::load(Path::new(file)).unwrap_or_else(|msg| {
+            eprintln!("Can not load policy {}: {}", underline!(file), msg);
+            process::exit(1);
+        })
+    });
+    let stats_only = args.is_present("stats-only");
+    let format = if args.is_present("quiet") || stats_only {
+        Format::Quiet
+    } else if args.is_present("json") {
+        Format::Json
+    } else if args.is_present("ndjson") {
+        Format::Ndjson
+    } else if args.is_present("csv") {
+        Format::Csv
+    } else if args.is_present("sarif") {
+        Format::Sarif
+    } else if args.is_present("table") {
+        Format::Table
+    } else if args.is_present("grid") {
+        Format::Grid
+    } else if args.is_present("yaml") {
+        Format::Yaml
+    } else {
+        Format::Text
+    };
+    let arch = args.value_of("arch");
+    let ignore_errors = args.is_present("ignore-errors");
+    let insecure_only = args.is_present("insecure-only");
+    let group_by = args.value_of("group-by") == Some("type");
+    let collapse_fat = args.is_present("collapse-fat");
+    let dedup = args.is_present("dedup");
+    let timeout = args
+        .value_of("timeout")
+        .and_then(|timeout| timeout.parse().ok())
+        .map(Duration::from_millis);
+    let selected = args
+        .value_of("fields")
+        .map(|list| fields::parse(list).unwrap_or_default());
+    let files = args.values_of("file");
+    let directories = args.values_of("directory");
     let pretty = args.is_present("pretty");
+    let max_file_size =
+        args.value_of("max-file-size").and_then(|size| parse_size(size).ok());
+    let verbose = args.is_present("verbose");
+    let cache = args.value_of("cache").map(Cache::load);
     let procids = args.value_of("pid");
     let procname = args.value_of("process");
     let procall = args.is_present("process-all");
 
-    if procall {
+    if let Some(jobs) = args.value_of("jobs") {
+        if let Err(msg) = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs.parse().unwrap_or_default())
+            .build_global()
+        {
+            eprintln!("Can not set the number of jobs: {}", msg);
+            process::exit(1);
+        }
+    }
+
+    let mut regressed = false;
+    let scanned: Vec<Binary> = if let Some(mut pair) = args.values_of("diff") {
+        let parse = |file: &str| match parse_path_with_limit(
+            Path::new(file),
+            max_file_size,
+        ) {
+            Ok(results) => select_arch(file, results, arch),
+            Err(ParseError::FatArch { binaries, errors }) => {
+                eprintln!(
+                    "Can not parse binary file {}: {}",
+                    underline!(file),
+                    ParseError::FatArch { binaries: Vec::new(), errors }
+                );
+                select_arch(file, binaries, arch)
+            }
+            Err(msg) => {
+                eprintln!(
+                    "Can not parse binary file {}: {}",
+                    underline!(file),
+                    msg
+                );
+                process::exit(1);
+            }
+        };
+        let old = parse(pair.next().unwrap_or_default());
+        let new = parse(pair.next().unwrap_or_default());
+        regressed = diff::print(&old, &new);
+        new
+    } else if procall {
         let system =
             System::new_with_specifics(RefreshKind::new().with_processes());
-        let mut procs: Vec<Process> = Vec::new();
+        // processes running the same executable are only scanned once
+        let mut executables: HashMap<&Path, Vec<Pid>> = HashMap::new();
         for (pid, proc_entry) in system.processes() {
-            if let Ok(results) = parse(proc_entry.exe()) {
-                if json {
-                    #[allow(clippy::cast_sign_loss)]
-                    procs.append(&mut vec![Process::new(
-                        *pid as usize,
-                        results,
-                    )]);
-                } else {
+            // kernel threads and unreadable processes have no executable
+            if !proc_entry.exe().as_os_str().is_empty() {
+                executables.entry(proc_entry.exe()).or_default().push(*pid);
+            }
+        }
+        let mut executables: Vec<(&Path, Vec<Pid>)> = executables
+            .into_iter()
+            .map(|(exe, mut pids)| {
+                pids.sort_unstable();
+                (exe, pids)
+            })
+            .collect();
+        executables.sort_unstable_by_key(|(_, pids)| pids[0]);
+        let mut procs: Vec<Process> = Vec::new();
+        for (exe, pids) in executables {
+            let proc_entry = match system.process(pids[0]) {
+                Some(proc_entry) => proc_entry,
+                None => continue,
+            };
+            if let Ok(mut results) = parse_path_with_limit(
+                &process_exe(pids[0], proc_entry),
+                max_file_size,
+            ) {
+                for result in &mut results {
+                    result.file = exe.display().to_string();
+                }
+                strip_section_entropy(&mut results, verbose, None);
+                if format == Format::Ndjson {
+                    ndjson_print(&results, false, None, false);
+                }
+                if format == Format::Text {
+                    let pid_list: Vec<String> =
+                        pids.iter().map(ToString::to_string).collect();
                     for result in &results {
-                        println!(
+                        outln!(
                             "{}({})\n \u{21aa} {}",
                             proc_entry.name(),
-                            pid,
-                            result
+                            pid_list.join(", "),
+                            text(result, verbose)
                         );
                     }
                 }
+                #[allow(clippy::cast_sign_loss)]
+                let mut proc = Process::new(pids[0] as usize, results);
+                #[allow(clippy::cast_sign_loss)]
+                proc.pids.extend(pids.iter().map(|pid| *pid as usize));
+                procs.push(proc);
             }
         }
-        if json {
-            json_print(&json!(Processes::new(procs)), pretty);
+        let procs = Processes::new(procs);
+        match format {
+            Format::Json => json_print(&json!(procs), pretty),
+            Format::Table => table::print(
+                procs.processes.iter().flat_map(|proc| &proc.binary),
+            ),
+            Format::Grid => grid::print(
+                procs.processes.iter().flat_map(|proc| &proc.binary),
+            ),
+            Format::Yaml => yaml_print(&procs),
+            Format::Csv => csv::print(
+                procs.processes.iter().flat_map(|proc| &proc.binary),
+            ),
+            Format::Sarif => json_print(
+                &sarif::log(
+                    procs.processes.iter().flat_map(|proc| &proc.binary),
+                ),
+                pretty,
+            ),
+            Format::Ndjson | Format::Text | Format::Quiet => (),
         }
+        procs.processes.into_iter().flat_map(|proc| proc.binary).collect()
     } else if let Some(procids) = procids {
-        let procids: Vec<sysinfo::Pid> = procids
+        let procids: Vec<Pid> = procids
             .split(',')
-            .map(|id| match id.parse::<sysinfo::Pid>() {
+            .map(|id| match id.parse::<Pid>() {
                 Ok(id) => id,
                 Err(msg) => {
                     eprintln!("Invalid process ID {}: {}", id, msg);
@@ -271,7 +1582,7 @@ fn main() {
             .collect();
         let system =
             System::new_with_specifics(RefreshKind::new().with_processes());
-
+        let mut bins: Vec<Binary> = Vec::new();
         for procid in procids {
             let process = if let Some(process) = system.process(procid) {
                 process
@@ -280,35 +1591,66 @@ fn main() {
                 continue;
             };
 
-            if !process.exe().is_file() {
-                eprintln!(
-                    "No valid executable found for process {} with ID {}: {}",
-                    process.name(),
-                    procid,
-                    process.exe().display()
-                );
-                continue;
-            }
-
-            match parse(process.exe()) {
-                Ok(results) => {
-                    if json {
+            match parse_path_with_limit(
+                &process_exe(procid, process),
+                max_file_size,
+            ) {
+                Ok(mut results) => {
+                    let label = format!(
+                        "{} (pid {})",
+                        process.exe().display(),
+                        procid
+                    );
+                    for result in &mut results {
+                        result.file = label.clone();
+                    }
+                    strip_section_entropy(&mut results, verbose, None);
+                    if matches!(format, Format::Json | Format::Yaml) {
                         #[allow(clippy::cast_sign_loss)]
-                        json_print(
-                            &json!(Process::new(procid as usize, results)),
-                            pretty,
-                        );
+                        let proc = Process::new(procid as usize, results);
+                        if format == Format::Json {
+                            json_print(&json!(proc), pretty);
+                        } else {
+                            yaml_print(&proc);
+                        }
+                        bins.extend(proc.binary);
                     } else {
-                        for result in &results {
-                            println!(
-                                "{}({})\n \u{21aa} {}",
-                                process.name(),
-                                process.pid(),
-                                result
-                            );
+                        if format == Format::Ndjson {
+                            ndjson_print(&results, false, None, false);
                         }
+                        if format == Format::Text {
+                            for result in &results {
+                                outln!(
+                                    "{}({})\n \u{21aa} {}",
+                                    process.name(),
+                                    process.pid(),
+                                    text(result, verbose)
+                                );
+                            }
+                        }
+                        bins.extend(results);
                     }
                 }
+                Err(ParseError::Goblin(Error::IO(err)))
+                    if err.kind() == io::ErrorKind::PermissionDenied =>
+                {
+                    eprintln!(
+                        "Permission denied reading the executable of process \
+                         {} with ID {} [requires root or the same user as \
+                         the process]",
+                        process.name(),
+                        procid
+                    );
+                }
+                Err(ParseError::Goblin(Error::IO(err)))
+                    if err.kind() == io::ErrorKind::NotFound =>
+                {
+                    eprintln!(
+                        "No valid executable found for process {} with ID {}",
+                        process.name(),
+                        procid
+                    );
+                }
                 Err(msg) => {
                     eprintln!(
                         "Can not parse process {} with ID {}: {}",
@@ -320,6 +1662,14 @@ fn main() {
                 }
             }
         }
+        match format {
+            Format::Csv => csv::print(&bins),
+            Format::Sarif => json_print(&sarif::log(&bins), pretty),
+            Format::Table => table::print(&bins),
+            Format::Grid => grid::print(&bins),
+            _ => (),
+        }
+        bins
     } else if let Some(procname) = procname {
         let system =
             System::new_with_specifics(RefreshKind::new().with_processes());
@@ -330,66 +1680,231 @@ fn main() {
         }
         let mut procs: Vec<Process> = Vec::new();
         for proc_entry in &sysprocs {
-            if let Ok(results) = parse(proc_entry.exe()) {
-                if json {
-                    #[allow(clippy::cast_sign_loss)]
-                    procs.append(&mut vec![Process::new(
-                        proc_entry.pid() as usize,
-                        results,
-                    )]);
-                } else {
+            if let Ok(mut results) =
+                parse_path_with_limit(proc_entry.exe(), max_file_size)
+            {
+                strip_section_entropy(&mut results, verbose, None);
+                if format == Format::Ndjson {
+                    ndjson_print(&results, false, None, false);
+                }
+                if format == Format::Text {
                     for result in &results {
-                        println!(
+                        outln!(
                             "{}({})\n \u{21aa} {}",
                             proc_entry.name(),
                             proc_entry.pid(),
-                            result
+                            text(result, verbose)
                         );
                     }
                 }
+                #[allow(clippy::cast_sign_loss)]
+                procs.push(Process::new(proc_entry.pid() as usize, results));
             }
         }
-        if json {
-            json_print(&json!(Processes::new(procs)), pretty);
+        let procs = Processes::new(procs);
+        match format {
+            Format::Json => json_print(&json!(procs), pretty),
+            Format::Table => table::print(
+                procs.processes.iter().flat_map(|proc| &proc.binary),
+            ),
+            Format::Grid => grid::print(
+                procs.processes.iter().flat_map(|proc| &proc.binary),
+            ),
+            Format::Yaml => yaml_print(&procs),
+            Format::Csv => csv::print(
+                procs.processes.iter().flat_map(|proc| &proc.binary),
+            ),
+            Format::Sarif => json_print(
+                &sarif::log(
+                    procs.processes.iter().flat_map(|proc| &proc.binary),
+                ),
+                pretty,
+            ),
+            Format::Ndjson | Format::Text | Format::Quiet => (),
         }
-    } else if let Some(directory) = directory {
-        let directory_path = Path::new(directory);
+        procs.processes.into_iter().flat_map(|proc| proc.binary).collect()
+    } else if let Some(directories) = directories {
+        let directory_paths: Vec<&Path> = directories.map(Path::new).collect();
 
-        if !directory_path.is_dir() {
-            eprintln!("Directory {} not found", underline!(directory));
-            process::exit(1);
+        for directory_path in &directory_paths {
+            if !directory_path.is_dir() {
+                eprintln!(
+                    "Directory {} not found",
+                    underline!(directory_path.display().to_string())
+                );
+                process::exit(1);
+            }
         }
 
-        walk(directory_path, json, pretty);
-    } else if let Some(file) = file {
-        let file_path = Path::new(file);
+        let options = WalkOptions {
+            follow_symlinks: args.is_present("follow-symlinks"),
+            no_ignore: args.is_present("no-ignore"),
+            max_depth: args
+                .value_of("max-depth")
+                .and_then(|depth| depth.parse().ok()),
+            max_file_size,
+            dedup,
+            progress: args.is_present("progress"),
+            timeout,
+            include_ext: parse_extensions(args.value_of("include-ext")),
+            exclude_ext: parse_extensions(args.value_of("exclude-ext")),
+            overrides: path_filters(
+                directory_paths[0],
+                args.value_of("include-from"),
+                args.value_of("exclude-from"),
+            ),
+        };
+        walk(
+            &directory_paths,
+            &options,
+            format,
+            pretty,
+            verbose,
+            insecure_only,
+            group_by,
+            collapse_fat,
+            selected.as_deref(),
+            ignore_errors,
+            arch,
+            cache.as_ref(),
+        )
+    } else if let Some(files) = files {
+        let globbing = args.is_present("glob");
+        let files: Vec<String> = if globbing {
+            expand_globs(files)
+        } else {
+            files.map(str::to_string).collect()
+        };
+        let mut scanned: Vec<(String, Vec<Binary>)> = Vec::new();
+        for file in &files {
+            let file = file.as_str();
+            let file_path = Path::new(file);
 
-        if !file_path.is_file() {
-            eprintln!("File {} not found", underline!(file));
-            process::exit(1);
-        }
+            if file != "-" && !is_url(file) && !file_path.is_file() {
+                if ignore_errors {
+                    continue;
+                }
+                eprintln!("File {} not found", underline!(file));
+                process::exit(1);
+            }
 
-        match parse(file_path) {
-            Ok(results) => {
-                if json {
-                    json_print(&json!(Binaries::new(results)), pretty);
-                } else {
-                    for result in &results {
-                        println!("{}", result);
+            let results = if file == "-" {
+                parse_stdin()
+            } else if is_url(file) {
+                parse_url(file, max_file_size)
+            } else {
+                parse_cached(
+                    cache.as_ref(),
+                    file_path,
+                    max_file_size,
+                    dedup,
+                    timeout,
+                )
+            };
+            match results {
+                Ok(results) => scanned.push((
+                    file.to_string(),
+                    select_arch(file, results, arch),
+                )),
+                Err(ParseError::FatArch { binaries, errors }) => {
+                    scanned.push((
+                        file.to_string(),
+                        select_arch(file, binaries, arch),
+                    ));
+                    if !ignore_errors {
+                        eprintln!(
+                            "Can not parse binary file {}: {}",
+                            underline!(file),
+                            ParseError::FatArch {
+                                binaries: Vec::new(),
+                                errors
+                            }
+                        );
                     }
                 }
-            }
-            Err(msg) => {
-                eprintln!(
+                // like directories, patterns also match scripts and data
+                Err(
+                    ParseError::Empty
+                    | ParseError::TooSmall(_)
+                    | ParseError::Goblin(Error::BadMagic(_)),
+                ) if globbing => (),
+                // keep checking the remaining files
+                Err(_) if ignore_errors => (),
+                Err(ParseError::TimedOut(timeout)) => eprintln!(
                     "Can not parse binary file {}: {}",
                     underline!(file),
-                    msg
-                );
-                process::exit(1);
+                    ParseError::TimedOut(timeout)
+                ),
+                Err(msg) => {
+                    eprintln!(
+                        "Can not parse binary file {}: {}",
+                        underline!(file),
+                        msg
+                    );
+                    process::exit(1);
+                }
             }
         }
+        let bins: Vec<Binary> = if dedup {
+            dedup::dedup(scanned)
+        } else {
+            scanned.into_iter().flat_map(|(_, binaries)| binaries).collect()
+        };
+        print_binaries(
+            bins,
+            format,
+            pretty,
+            verbose,
+            insecure_only,
+            group_by,
+            collapse_fat,
+            selected.as_deref(),
+        )
     } else {
-        eprintln!("{}", args.usage());
+        Vec::new()
+    };
+
+    if let Some(cache) = cache {
+        if let Err(err) = cache.save() {
+            eprintln!(
+                "Can not write cache {}: {}",
+                underline!(args.value_of("cache").unwrap_or_default()),
+                err
+            );
+        }
+    }
+
+    if args.is_present("summary") || stats_only {
+        let summary = summary::summarize(&scanned);
+        // keep machine readable output on stdout parseable
+        if matches!(
+            format,
+            Format::Text | Format::Table | Format::Grid | Format::Quiet
+        ) {
+            out!("{}", summary);
+        } else {
+            eprint!("{}", summary);
+        }
+    }
+
+    if let Some(file) = args.value_of("html") {
+        if let Err(err) = fs::write(file, html::report(&scanned)) {
+            eprintln!("Can not write {}: {}", underline!(file), err);
+            process::exit(1);
+        }
+    }
+
+    if let Some(required) = required {
+        if !enforce(&scanned, &required) {
+            process::exit(1);
+        }
+    }
+    if let Some(policy) = policy {
+        if !enforce_policy(&scanned, &policy) {
+            process::exit(1);
+        }
+    }
+    if regressed {
         process::exit(1);
     }
 }
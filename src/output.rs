@@ -0,0 +1,67 @@
+//! Destination of the results, stdout or the file given with `--output`
+//!
+//! progress, errors and policy violations always go to stderr, only the
+//! results of the chosen format are written here
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::{fmt, process};
+
+/// file given with `--output` and its path for error messages
+static FILE: OnceLock<(String, Mutex<LineWriter<File>>)> = OnceLock::new();
+
+/// write all further results to `path`, truncating an existing file
+pub fn to_file(path: &str) -> io::Result<()> {
+    let file = File::create(path)?;
+    FILE.set((path.to_string(), Mutex::new(LineWriter::new(file)))).ok();
+    Ok(())
+}
+
+/// results are written to a file instead of stdout
+#[cfg(feature = "color")]
+pub fn is_file() -> bool {
+    FILE.get().is_some()
+}
+
+/// write formatted results to the destination
+///
+/// the file is line buffered, results have to end with a line break to be
+/// written before the process exits
+pub fn write(args: fmt::Arguments<'_>) -> io::Result<()> {
+    match FILE.get() {
+        Some((_, file)) => {
+            file.lock().unwrap_or_else(PoisonError::into_inner).write_fmt(args)
+        }
+        None => io::stdout().write_fmt(args),
+    }
+}
+
+/// report a failed write and exit, a results file missing some of the
+/// binaries must not look complete
+pub fn fail(err: &io::Error) -> ! {
+    match FILE.get() {
+        Some((path, _)) => eprintln!("Can not write to {}: {}", path, err),
+        None => eprintln!("Can not write to stdout: {}", err),
+    }
+    process::exit(1);
+}
+
+/// `print!` to the destination of the results
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::output::write(format_args!($($arg)*))
+            .unwrap_or_else(|err| $crate::output::fail(&err))
+    };
+}
+
+/// `println!` to the destination of the results
+macro_rules! outln {
+    () => {
+        $crate::output::out!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::output::out!("{}\n", format_args!($($arg)*))
+    };
+}
+
+pub(crate) use {out, outln};
@@ -1,15 +1,28 @@
 //! Implements checksec for PE32/32+ binaries
 #[cfg(feature = "color")]
 use colored::Colorize;
-use goblin::pe::utils::get_data;
+use goblin::pe::header::{
+    COFF_MACHINE_ARM, COFF_MACHINE_ARM64, COFF_MACHINE_ARMNT,
+    COFF_MACHINE_IA64, COFF_MACHINE_RISCV32, COFF_MACHINE_RISCV64,
+    COFF_MACHINE_THUMB, COFF_MACHINE_X86, COFF_MACHINE_X86_64,
+};
+use goblin::pe::options::ParseOptions;
+use goblin::pe::section_table::{IMAGE_SCN_MEM_EXECUTE, IMAGE_SCN_MEM_WRITE};
+use goblin::pe::utils::find_offset;
 use goblin::pe::PE;
-use memmap::Mmap;
+use schemars::JsonSchema;
+use scroll::{Pread as _, LE};
 use scroll_derive::Pread;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::entropy::{self, SectionEntropy};
+#[cfg(not(feature = "color"))]
+use crate::option_bool;
 #[cfg(feature = "color")]
-use crate::colorize_bool;
+use crate::{colorize_bool, colorize_option_bool};
+
+const IMAGE_FILE_RELOCS_STRIPPED: u16 = 0x0001;
 
 const IMAGE_DLLCHARACTERISTICS_HIGH_ENTROPY_VA: u16 = 0x0020;
 const IMAGE_DLLCHARACTERISTICS_DYNAMIC_BASE: u16 = 0x0040;
@@ -19,6 +32,24 @@ const IMAGE_DLLCHARACTERISTICS_NO_ISOLATION: u16 = 0x0200;
 const IMAGE_DLLCHARACTERISTICS_NO_SEH: u16 = 0x0400;
 const IMAGE_DLLCHARACTERISTICS_GUARD_CF: u16 = 0x4000;
 
+// `Subsystem` values in `IMAGE_OPTIONAL_HEADER32/64`
+const IMAGE_SUBSYSTEM_NATIVE: u16 = 1;
+const IMAGE_SUBSYSTEM_WINDOWS_GUI: u16 = 2;
+const IMAGE_SUBSYSTEM_WINDOWS_CUI: u16 = 3;
+const IMAGE_SUBSYSTEM_OS2_CUI: u16 = 5;
+const IMAGE_SUBSYSTEM_POSIX_CUI: u16 = 7;
+const IMAGE_SUBSYSTEM_NATIVE_WINDOWS: u16 = 8;
+const IMAGE_SUBSYSTEM_WINDOWS_CE_GUI: u16 = 9;
+const IMAGE_SUBSYSTEM_EFI_APPLICATION: u16 = 10;
+const IMAGE_SUBSYSTEM_EFI_BOOT_SERVICE_DRIVER: u16 = 11;
+const IMAGE_SUBSYSTEM_EFI_RUNTIME_DRIVER: u16 = 12;
+const IMAGE_SUBSYSTEM_EFI_ROM: u16 = 13;
+const IMAGE_SUBSYSTEM_XBOX: u16 = 14;
+const IMAGE_SUBSYSTEM_WINDOWS_BOOT_APPLICATION: u16 = 16;
+
+// `wCertificateType` of a `WIN_CERTIFICATE`
+const WIN_CERT_TYPE_PKCS_SIGNED_DATA: u16 = 0x0002;
+
 // stored in `IMAGE_LOAD_CONFIG_DIRECTORY64`
 const IMAGE_GUARD_RF_INSTRUMENTED: u32 = 0x0002_0000;
 const IMAGE_GUARD_RF_ENABLE: u32 = 0x0004_0000;
@@ -203,12 +234,256 @@ impl From<ImageLoadConfigDirectory32> for ImageLoadConfigDirectory {
     }
 }
 
-/// Address Space Layout Randomization: `None`, `DYNBASE`, or `HIGHENTROPYVA`
-#[derive(Deserialize, Serialize, Debug)]
+/// [`WIN_CERTIFICATE`](https://docs.microsoft.com/en-us/windows/win32/api/wintrust/ns-wintrust-win_certificate)
+/// header of the first entry in the attribute certificate table
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct Certificate {
+    /// `wCertificateType`, `0x0002` for `WIN_CERT_TYPE_PKCS_SIGNED_DATA`
+    pub certificate_type: u16,
+    /// `dwLength` of the certificate entry
+    pub length: u32,
+    /// `wRevision`, `0x0200` for `WIN_CERT_REVISION_2_0`
+    pub revision: u16,
+    /// size of the certificate table (`IMAGE_DIRECTORY_ENTRY_SECURITY`)
+    pub table_size: u32,
+}
+
+/// location of an `IMAGE_DATA_DIRECTORY` entry
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct DataDirectory {
+    /// relative virtual address of the table
+    pub rva: u32,
+    /// size of the table
+    pub size: u32,
+}
+
+/// section holding the `IMAGE_DIRECTORY_ENTRY_IAT` import address table
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct IatSection {
+    /// `Characteristics` of the section header
+    pub characteristics: u32,
+    /// section name, e.g. `.rdata` or `.idata`
+    pub name: String,
+}
+
+/// offset of `CheckSum` within the `IMAGE_OPTIONAL_HEADER32/64`
+const OPTIONAL_HEADER_CHECKSUM_OFFSET: usize = 64;
+
+/// `Rich` marker ending the Rich header, followed by the xor key
+const RICH_MARKER: u32 = 0x6863_6952;
+/// `DanS` marker starting the Rich header once decoded
+const DANS_MARKER: u32 = 0x536e_6144;
+
+/// Rich header entry, the number of objects built by one MSVC tool
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+pub struct RichEntry {
+    /// product (tool) identifier
+    pub product_id: u16,
+    /// build number of the tool
+    pub build: u16,
+    /// number of objects
+    pub count: u32,
+}
+
+/// upper bound for the size of any `IMAGE_LOAD_CONFIG_DIRECTORY` layout
+const LOAD_CONFIG_MAX_SIZE: usize = 0x200;
+
+/// read the `IMAGE_LOAD_CONFIG_DIRECTORY32/64` matching the image bitness
+///
+/// older toolchains emit shorter directories, fields beyond the `size`
+/// recorded in the directory itself read as zero
+fn get_load_config(pe: &PE, mem: &[u8]) -> Option<ImageLoadConfigDirectory> {
+    let optional_header = pe.header.optional_header?;
+    let load_config_hdr =
+        (*optional_header.data_directories.get_load_config_table())?;
+    let offset = find_offset(
+        load_config_hdr.virtual_address as usize,
+        &pe.sections,
+        optional_header.windows_fields.file_alignment,
+        &ParseOptions::default(),
+    )?;
+    let size: u32 = mem.pread_with(offset, LE).ok()?;
+    let len =
+        (size as usize).min(mem.len() - offset).min(LOAD_CONFIG_MAX_SIZE);
+    let mut buf = [0_u8; LOAD_CONFIG_MAX_SIZE];
+    buf[..len].copy_from_slice(&mem[offset..offset + len]);
+    if pe.is_64 {
+        buf.pread_with::<ImageLoadConfigDirectory64>(0, LE).ok()
+    } else {
+        buf.pread_with::<ImageLoadConfigDirectory32>(0, LE)
+            .ok()
+            .map(Into::into)
+    }
+}
+
+/// offset of the `ManagedNativeHeader` data directory in the
+/// `IMAGE_COR20_HEADER`
+const COR20_MANAGED_NATIVE_HEADER: usize = 64;
+/// offset of `Flags` in the `IMAGE_COR20_HEADER`
+const COR20_FLAGS: usize = 16;
+/// `COMIMAGE_FLAGS_IL_LIBRARY`, set on NGEN native images
+const COMIMAGE_FLAGS_IL_LIBRARY: u32 = 0x4;
+/// `Signature` of the `READYTORUN_HEADER`, `RTR` in little endian
+const READYTORUN_SIGNATURE: u32 = 0x0052_5452;
+
+/// check the `IMAGE_COR20_HEADER` referenced by the
+/// `IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR` data directory for precompiled
+/// native code, either a `READYTORUN_HEADER` in the `ManagedNativeHeader`
+/// or an NGEN image flagged with `COMIMAGE_FLAGS_IL_LIBRARY`
+fn has_native_code(pe: &PE, mem: &[u8]) -> Option<bool> {
+    let optional_header = pe.header.optional_header?;
+    let directory =
+        (*optional_header.data_directories.get_clr_runtime_header())?;
+    let offset_of = |rva: usize| {
+        find_offset(
+            rva,
+            &pe.sections,
+            optional_header.windows_fields.file_alignment,
+            &ParseOptions::default(),
+        )
+    };
+    let header = offset_of(directory.virtual_address as usize)?;
+    let flags: u32 = mem.pread_with(header + COR20_FLAGS, LE).ok()?;
+    let native_rva: u32 =
+        mem.pread_with(header + COR20_MANAGED_NATIVE_HEADER, LE).ok()?;
+    if native_rva == 0 {
+        return Some(false);
+    }
+    if flags & COMIMAGE_FLAGS_IL_LIBRARY != 0 {
+        return Some(true);
+    }
+    let native = offset_of(native_rva as usize)?;
+    let signature: u32 = mem.pread_with(native, LE).ok()?;
+    Some(signature == READYTORUN_SIGNATURE)
+}
+
+/// size of an `IMAGE_DELAYLOAD_DESCRIPTOR`
+const DELAYLOAD_DESCRIPTOR_SIZE: usize = 32;
+/// `Attributes` flag of descriptors holding RVAs instead of virtual
+/// addresses
+const DELAYLOAD_RVA_BASED: u32 = 0x1;
+
+/// read the DLL names of the `IMAGE_DELAYLOAD_DESCRIPTOR` table referenced by
+/// the `IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT` data directory
+fn get_delay_imports(pe: &PE, mem: &[u8]) -> Option<Vec<String>> {
+    let optional_header = pe.header.optional_header?;
+    let directory =
+        (*optional_header.data_directories.get_delay_import_descriptor())?;
+    let offset_of = |rva: usize| {
+        find_offset(
+            rva,
+            &pe.sections,
+            optional_header.windows_fields.file_alignment,
+            &ParseOptions::default(),
+        )
+    };
+    let mut offset = offset_of(directory.virtual_address as usize)?;
+    let mut names: Vec<String> = Vec::new();
+    // the table ends with a zeroed descriptor
+    while let (Ok(attributes), Ok(name)) = (
+        mem.pread_with::<u32>(offset, LE),
+        mem.pread_with::<u32>(offset + 4, LE),
+    ) {
+        if name == 0 {
+            break;
+        }
+        let rva = if attributes & DELAYLOAD_RVA_BASED == 0 {
+            (name as usize).wrapping_sub(pe.image_base)
+        } else {
+            name as usize
+        };
+        if let Some(name) =
+            offset_of(rva).and_then(|offset| mem.pread::<&str>(offset).ok())
+        {
+            names.push(name.to_string());
+        }
+        offset += DELAYLOAD_DESCRIPTOR_SIZE;
+    }
+    Some(names)
+}
+
+/// `CheckSumMappedFile` of `mem` with the `CheckSum` dword at file offset
+/// `field` left out, a trailing odd byte is summed as a word padded with
+/// zero
+fn image_checksum(mem: &[u8], field: usize) -> u32 {
+    let mut sum: u64 = 0;
+    for (idx, word) in mem.chunks(2).enumerate() {
+        if (field..field + 4).contains(&(idx * 2)) {
+            continue;
+        }
+        sum += u64::from(word[0])
+            | u64::from(word.get(1).copied().unwrap_or(0)) << 8;
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    sum = (sum & 0xffff) + (sum >> 16);
+    #[allow(clippy::cast_possible_truncation)]
+    let checksum = (sum as u32).wrapping_add(mem.len() as u32);
+    checksum
+}
+
+/// offset of `AddressOfCallBacks` in the `IMAGE_TLS_DIRECTORY32`, three
+/// pointer sized fields precede it
+const TLS_CALLBACKS_OFFSET32: usize = 12;
+/// offset of `AddressOfCallBacks` in the `IMAGE_TLS_DIRECTORY64`
+const TLS_CALLBACKS_OFFSET64: usize = 24;
+/// upper bound for the callbacks read from a corrupt array without a
+/// terminating null pointer
+const MAX_TLS_CALLBACKS: u32 = 1024;
+
+/// count the callbacks of the null terminated `AddressOfCallBacks` array
+/// of the `IMAGE_TLS_DIRECTORY32/64` referenced by the
+/// `IMAGE_DIRECTORY_ENTRY_TLS` data directory
+fn get_tls_callbacks(pe: &PE, mem: &[u8]) -> Option<u32> {
+    let optional_header = pe.header.optional_header?;
+    let directory = (*optional_header.data_directories.get_tls_table())?;
+    let offset_of = |rva: usize| {
+        find_offset(
+            rva,
+            &pe.sections,
+            optional_header.windows_fields.file_alignment,
+            &ParseOptions::default(),
+        )
+    };
+    let tls = offset_of(directory.virtual_address as usize)?;
+    // virtual addresses of the array and of each callback
+    let read = |offset: usize| -> Option<u64> {
+        if pe.is_64 {
+            mem.pread_with::<u64>(offset, LE).ok()
+        } else {
+            mem.pread_with::<u32>(offset, LE).ok().map(u64::from)
+        }
+    };
+    let pointer = if pe.is_64 { 8 } else { 4 };
+    let callbacks = read(
+        tls + if pe.is_64 {
+            TLS_CALLBACKS_OFFSET64
+        } else {
+            TLS_CALLBACKS_OFFSET32
+        },
+    )?;
+    if callbacks == 0 {
+        return Some(0);
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let mut offset =
+        offset_of(callbacks.wrapping_sub(pe.image_base as u64) as usize)?;
+    let mut count = 0;
+    while count < MAX_TLS_CALLBACKS && read(offset).is_some_and(|va| va != 0) {
+        count += 1;
+        offset += pointer;
+    }
+    Some(count)
+}
+
+/// Address Space Layout Randomization: `None`, `DYNBASE`, `HIGHENTROPYVA`,
+/// or `NoRelocs` when `DYNAMIC_BASE` is declared but the relocations were
+/// stripped (`/FIXED`), so the image can not be rebased
+#[derive(Deserialize, JsonSchema, Serialize, Debug)]
 pub enum ASLR {
     None,
     DynamicBase,
     HighEntropyVa,
+    NoRelocs,
 }
 impl fmt::Display for ASLR {
     #[cfg(not(feature = "color"))]
@@ -220,6 +495,7 @@ impl fmt::Display for ASLR {
                 Self::None => "None",
                 Self::DynamicBase => "DYNBASE",
                 Self::HighEntropyVa => "HIGHENTROPYVA",
+                Self::NoRelocs => "NO RELOCS",
             }
         )
     }
@@ -232,18 +508,97 @@ impl fmt::Display for ASLR {
                 Self::None => "None".red(),
                 Self::DynamicBase => "DYNBASE".yellow(),
                 Self::HighEntropyVa => "HIGHENTROPYVA".bright_green(),
+                Self::NoRelocs => "NO RELOCS".red(),
             }
         )
     }
 }
 
+/// `CheckSum` of the `IMAGE_OPTIONAL_HEADER32/64`: `Valid`, `Invalid`
+/// (tampered with or stale), or `Zero` when the linker never filled it in
+#[derive(Deserialize, JsonSchema, Serialize, Debug, PartialEq)]
+pub enum Checksum {
+    Valid,
+    Invalid,
+    Zero,
+}
+impl fmt::Display for Checksum {
+    #[cfg(not(feature = "color"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<7}",
+            match *self {
+                Self::Valid => "valid",
+                Self::Invalid => "invalid",
+                Self::Zero => "zero",
+            }
+        )
+    }
+    #[cfg(feature = "color")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<7}",
+            match *self {
+                Self::Valid => "valid".green(),
+                Self::Invalid => "invalid".red(),
+                Self::Zero => "zero".yellow(),
+            }
+        )
+    }
+}
+
+/// Structured Exception Handling of 32-bit images: `No SEH`, `Unsafe`,
+/// `SafeSEH`, or `N/A` for 64-bit images which use table based unwinding
+#[derive(Deserialize, JsonSchema, Serialize, Debug)]
+pub enum SEH {
+    NoSEH,
+    Unsafe,
+    SafeSEH,
+    NotApplicable,
+}
+impl fmt::Display for SEH {
+    #[cfg(not(feature = "color"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<7}",
+            match *self {
+                Self::NoSEH => "No SEH",
+                Self::Unsafe => "Unsafe",
+                Self::SafeSEH => "SafeSEH",
+                Self::NotApplicable => "N/A",
+            }
+        )
+    }
+    #[cfg(feature = "color")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<7}",
+            match *self {
+                Self::NoSEH => "No SEH".green(),
+                Self::Unsafe => "Unsafe".red(),
+                Self::SafeSEH => "SafeSEH".bright_green(),
+                Self::NotApplicable => "N/A".dimmed(),
+            }
+        )
+    }
+}
+
+/// native code mitigations mostly do not cover managed code
+const MANAGED_NOTE: &str = "(.NET assembly: GS/SafeSEH largely N/A)";
+/// note for .NET assemblies shipping precompiled native code
+const NATIVE_NOTE: &str = "(.NET ReadyToRun: native mitigations apply)";
+
 /// Checksec result struct for PE32/32+ binaries
 ///
 /// **Example**
 ///
 /// ```rust
-/// use checksec::pe::Properties;
-/// use goblin::pe::PE;
+/// use checksec::pe::CheckSecResults;
+/// use goblin::Object;
 /// use memmap::Mmap;
 /// use std::fs;
 ///
@@ -269,53 +624,290 @@ impl fmt::Display for ASLR {
 /// object and a read-only memory-mapped version of the original file
 /// must be provided for evaluating PE32/32+ binaries.
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+#[schemars(rename = "PeCheckSecResults")]
 pub struct CheckSecResults {
+    /// Machine architecture (`Machine` of the COFF file header)
+    pub arch: String,
     /// Address Space Layout Randomization
     pub aslr: ASLR,
     /// Authenticode
     pub authenticode: bool,
+    /// Embedded attribute certificate (`WIN_CERTIFICATE`)
+    pub certificate: Option<Certificate>,
     /// Control Flow Guard (`/guard:cf`)
     pub cfg: bool,
-    /// Common Language Runtime *(.NET Framework)*
+    /// Control Flow Guard function table populated
+    pub cfg_function_table: bool,
+    /// stored `CheckSum` compared against the checksum of the image
+    pub checksum: Checksum,
+    /// checksum computed over the image
+    pub checksum_computed: u32,
+    /// `CheckSum` stored in the `IMAGE_OPTIONAL_HEADER32/64`
+    pub checksum_stored: u32,
+    /// Common Language Runtime *(.NET Framework)*, managed code is not
+    /// compiled with native mitigations such as `/GS` or `/SAFESEH`
     pub clr: bool,
+    /// Control Flow Guard in effect, `cfg` backed by a populated function
+    /// table
+    pub control_flow_guard: bool,
     /// Data Execution Prevention
     pub dep: bool,
+    /// DLLs loaded on first use (`/DELAYLOAD`)
+    pub delay_imports: Vec<String>,
+    /// `DllCharacteristics` of the `IMAGE_OPTIONAL_HEADER32/64`
+    pub dll_characteristics: u16,
     /// Dynamic Base
     pub dynamic_base: bool,
     /// Force Integrity (`/INTEGRITYCHECK`)
     pub force_integrity: bool,
-    /// Buffer Security Check (`/GS`)
-    pub gs: bool,
-    /// 64-bit ASLR (`/HIGHENTROPYVA`)
-    pub high_entropy_va: bool,
+    /// Buffer Security Check (`/GS`), `None` without a load config
+    /// directory to read the security cookie from
+    pub gs: Option<bool>,
+    /// 64-bit ASLR (`/HIGHENTROPYVA`), `None` for 32-bit images
+    pub high_entropy_va: Option<bool>,
+    /// section the import address table is located in, `None` without an
+    /// `IMAGE_DIRECTORY_ENTRY_IAT` data directory
+    pub iat_section: Option<IatSection>,
+    /// import address table in a section that stays writable
+    /// (`IMAGE_SCN_MEM_WRITE`), the loader only write protects the IAT
+    /// again when it is in a read-only section
+    pub iat_writable: bool,
+    /// DLLs of the import directory
+    pub imports: Vec<String>,
+    /// .NET assembly, a CLR header
+    /// (`IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR`) is present, see
+    /// [`Self::is_managed`] for images without native code
+    pub is_dotnet: bool,
     /// Allow Isolation (`/ALLOWISOLATION`)
     pub isolation: bool,
+    /// `IMAGE_DIRECTORY_ENTRY_LOAD_CONFIG` data directory
+    pub load_config_directory: Option<DataDirectory>,
+    /// .NET assembly with precompiled native code *(ReadyToRun or NGEN)*,
+    /// native mitigations apply to it like to unmanaged code
+    pub r2r: bool,
+    /// base relocations to rebase the image are present
+    /// (`IMAGE_DIRECTORY_ENTRY_BASERELOC` without
+    /// `IMAGE_FILE_RELOCS_STRIPPED`)
+    pub relocations: bool,
     /// Return Flow Guard
     pub rfg: bool,
+    /// MSVC toolchain fingerprint from the Rich header, `None` for images
+    /// without one *(most non-MSVC linkers)*
+    pub rich_header: Option<Vec<RichEntry>>,
+    /// Sections that are both writable and executable
+    pub rwx: bool,
+    /// names of the writable and executable sections, e.g. a `.text`
+    /// made writable by a packer
+    pub rwx_sections: Vec<String>,
     /// Safe Structured Exception Handler (`/SAFESEH`)
-    pub safeseh: bool,
+    pub safeseh: SEH,
     /// Structured Exception Handler
     pub seh: bool,
+    /// Number of registered safe exception handlers, `None` for 64-bit
+    /// images
+    pub seh_handlers: Option<u64>,
+    /// entropy of the contents of the raw data of every section,
+    /// empty after [`crate::binary::Binary::strip_section_entropy`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub section_entropy: Vec<SectionEntropy>,
+    /// Subsystem required to run the image (`/SUBSYSTEM`)
+    pub subsystem: String,
+    /// TLS callbacks are registered, they run before the entry point of
+    /// the image and of every new thread
+    pub tls: bool,
+    /// Number of TLS callbacks in the `IMAGE_TLS_DIRECTORY32/64`, `0`
+    /// without an `IMAGE_DIRECTORY_ENTRY_TLS` data directory
+    pub tls_callbacks: u32,
+    /// likely misconfigurations that make the image look more hardened
+    /// than it is, e.g. `DYNAMIC_BASE` without relocations to rebase it
+    pub warnings: Vec<String>,
 }
 impl CheckSecResults {
+    /// managed code only, native mitigations such as `/GS` and `/SAFESEH`
+    /// do not apply
     #[must_use]
-    pub fn parse(pe: &PE, buffer: &Mmap) -> Self {
-        Self {
+    pub const fn is_managed(&self) -> bool {
+        self.clr && !self.r2r
+    }
+    #[must_use]
+    pub fn parse(pe: &PE, buffer: &[u8]) -> Self {
+        let rwx_sections = pe.has_rwx_sections();
+        let iat_section = pe.get_iat_section();
+        let tls_callbacks = pe.has_tls_callbacks(buffer);
+        let cfg = pe.has_cfg();
+        let cfg_function_table = pe.has_cfg_function_table(buffer);
+        let clr = pe.has_clr();
+        let mut results = Self {
+            arch: pe.get_arch(),
             aslr: pe.has_aslr(),
             authenticode: pe.has_authenticode(buffer),
-            cfg: pe.has_cfg(),
-            clr: pe.has_clr(),
+            certificate: pe.has_certificate(buffer),
+            cfg,
+            cfg_function_table,
+            checksum: pe.has_checksum(buffer),
+            checksum_computed: pe.get_checksum(buffer),
+            checksum_stored: pe.get_stored_checksum(),
+            clr,
+            control_flow_guard: cfg && cfg_function_table,
             dep: pe.has_dep(),
+            delay_imports: pe.get_delay_imports(buffer),
+            dll_characteristics: pe.get_dll_characteristics(),
             dynamic_base: pe.has_dynamic_base(),
             force_integrity: pe.has_force_integrity(),
-            gs: pe.has_gs(buffer),
-            high_entropy_va: pe.has_high_entropy_va(),
+            gs: if pe.has_load_config(buffer) {
+                Some(pe.has_gs(buffer))
+            } else {
+                None
+            },
+            high_entropy_va: if pe.is_64 {
+                Some(pe.has_high_entropy_va())
+            } else {
+                None
+            },
+            iat_writable: iat_section.as_ref().is_some_and(|section| {
+                section.characteristics & IMAGE_SCN_MEM_WRITE != 0
+            }),
+            iat_section,
+            imports: pe.get_imports(),
+            is_dotnet: clr,
             isolation: pe.has_isolation(),
+            load_config_directory: pe.get_load_config_directory(),
+            r2r: pe.has_r2r(buffer),
+            relocations: pe.has_relocations(),
             rfg: pe.has_rfg(buffer),
-            safeseh: pe.has_safe_seh(buffer),
+            rich_header: pe.get_rich_header(buffer),
+            rwx: !rwx_sections.is_empty(),
+            rwx_sections,
+            safeseh: pe.has_safe_seh_mode(buffer),
             seh: pe.has_seh(),
+            seh_handlers: if pe.is_64 {
+                None
+            } else {
+                Some(pe.has_seh_handlers(buffer))
+            },
+            section_entropy: pe.get_section_entropy(buffer),
+            subsystem: pe.get_subsystem(),
+            tls: tls_callbacks > 0,
+            tls_callbacks,
+            warnings: Vec::new(),
+        };
+        results.warnings = results.derive_warnings();
+        results
+    }
+    /// advisory checks combining the results of the base checks
+    fn derive_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.dynamic_base && !self.relocations {
+            warnings.push(
+                "DYNAMIC_BASE without a relocation directory, the image can \
+                 not be rebased"
+                    .to_string(),
+            );
+        }
+        if self.high_entropy_va == Some(true) && !self.dynamic_base {
+            warnings.push("HIGH_ENTROPY_VA without DYNAMIC_BASE".to_string());
+        }
+        if self.cfg && !self.dynamic_base {
+            warnings.push(
+                "CFG without DYNAMIC_BASE, the loader ignores it".to_string(),
+            );
+        }
+        if let Some(section) =
+            self.iat_section.as_ref().filter(|_| self.iat_writable)
+        {
+            warnings.push(format!(
+                "import address table in writable section {}",
+                section.name
+            ));
+        }
+        warnings
+    }
+    /// supporting detail for verbose output as `(label, evidence)` pairs
+    #[must_use]
+    pub fn evidence(&self) -> Vec<(&'static str, String)> {
+        let mut evidence = vec![
+            (
+                "Checksum",
+                format!(
+                    "stored {:#010x}, computed {:#010x}",
+                    self.checksum_stored, self.checksum_computed
+                ),
+            ),
+            (
+                "DllCharacteristics",
+                format!("{:#06x}", self.dll_characteristics),
+            ),
+        ];
+        if !self.imports.is_empty() {
+            evidence.push(("Imports", self.imports.join(", ")));
+        }
+        if let Some(section) = &self.iat_section {
+            evidence.push((
+                "IAT",
+                format!(
+                    "in {} ({:#010x}, {})",
+                    section.name,
+                    section.characteristics,
+                    if self.iat_writable { "writable" } else { "read-only" }
+                ),
+            ));
+        }
+        if !self.delay_imports.is_empty() {
+            evidence.push(("Delay Imports", self.delay_imports.join(", ")));
+        }
+        if let Some(directory) = &self.load_config_directory {
+            evidence.push((
+                "Load Config",
+                format!(
+                    "RVA {:#x} ({:#x} bytes)",
+                    directory.rva, directory.size
+                ),
+            ));
         }
+        match &self.rich_header {
+            Some(entries) => {
+                for entry in entries {
+                    evidence.push((
+                        "Rich Header",
+                        format!(
+                            "product {} build {} ({} objects)",
+                            entry.product_id, entry.build, entry.count
+                        ),
+                    ));
+                }
+            }
+            None => evidence.push(("Rich Header", "absent".to_string())),
+        }
+        for name in &self.rwx_sections {
+            evidence.push(("RWX Sections", name.clone()));
+        }
+        if let Some(handlers) = self.seh_handlers {
+            evidence.push(("SafeSEH", format!("{} handlers", handlers)));
+        }
+        if self.tls {
+            evidence.push((
+                "TLS Callbacks",
+                format!(
+                    "{} callbacks run before the entry point",
+                    self.tls_callbacks
+                ),
+            ));
+        }
+        if let Some(certificate) = &self.certificate {
+            evidence.push((
+                "Authenticode",
+                format!(
+                    "WIN_CERTIFICATE type {:#06x} revision {:#06x} \
+                     ({:#x} bytes)",
+                    certificate.certificate_type,
+                    certificate.revision,
+                    certificate.length
+                ),
+            ));
+        }
+        evidence.extend(entropy::evidence(&self.section_entropy));
+        evidence
     }
 }
 
@@ -324,39 +916,66 @@ impl fmt::Display for CheckSecResults {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "ASLR: {} Authenticode: {} CFG: {} CLR: {} DEP: {} \
-            Dynamic Base: {} Force Integrity: {} GS: {} \
-            High Entropy VA: {} Isolation: {} RFG: {} SafeSEH: {} SEH: {}",
+            "ASLR: {} Authenticode: {} CFG: {} CFG Table: {} Checksum: {} \
+            CLR: {} Control Flow Guard: {} Delay Imports: {:2} DEP: {} \
+            Dynamic Base: {} \
+            Force Integrity: {} GS: {} High Entropy VA: {} Imports: {:2} \
+            Isolation: {} RFG: {} RWX Sections: {} \
+            SafeSEH: {} SEH: {} Subsystem: {}",
             self.aslr,
             self.authenticode,
             self.cfg,
+            self.cfg_function_table,
+            self.checksum,
             self.clr,
+            self.control_flow_guard,
+            self.delay_imports.len(),
             self.dep,
             self.dynamic_base,
             self.force_integrity,
-            self.gs,
-            self.high_entropy_va,
+            option_bool!(self.gs),
+            option_bool!(self.high_entropy_va),
+            self.imports.len(),
             self.isolation,
             self.rfg,
+            self.rwx_sections.len(),
             self.safeseh,
-            self.seh
-        )
+            self.seh,
+            self.subsystem
+        )?;
+        if self.r2r {
+            write!(f, " {}", NATIVE_NOTE)?;
+        } else if self.clr {
+            write!(f, " {}", MANAGED_NOTE)?;
+        }
+        if !self.warnings.is_empty() {
+            write!(f, " Warnings: {}", self.warnings.join("; "))?;
+        }
+        Ok(())
     }
     #[cfg(feature = "color")]
     /// Colorized human readable format output
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{} {} {} {} {} {} {} {} {} {} {} {} {} {} \
-             {} {} {} {} {} {} {} {} {} {} {} {}",
+            "{} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} \
+             {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}",
             "ASLR:".bold(),
             self.aslr,
             "Authenticode:".bold(),
             colorize_bool!(self.authenticode),
             "CFG:".bold(),
             colorize_bool!(self.cfg),
+            "CFG Table:".bold(),
+            colorize_bool!(self.cfg_function_table),
+            "Checksum:".bold(),
+            self.checksum,
             "CLR:".bold(),
             colorize_bool!(self.clr),
+            "Control Flow Guard:".bold(),
+            colorize_bool!(self.control_flow_guard),
+            "Delay Imports:".bold(),
+            format_args!("{:2}", self.delay_imports.len()),
             "DEP:".bold(),
             colorize_bool!(self.dep),
             "Dynamic Base:".bold(),
@@ -364,18 +983,42 @@ impl fmt::Display for CheckSecResults {
             "Force Integrity:".bold(),
             colorize_bool!(self.force_integrity),
             "GS:".bold(),
-            colorize_bool!(self.gs),
+            colorize_option_bool!(self.gs),
             "High Entropy VA:".bold(),
-            colorize_bool!(self.high_entropy_va),
+            colorize_option_bool!(self.high_entropy_va),
+            "Imports:".bold(),
+            format_args!("{:2}", self.imports.len()),
             "Isolation:".bold(),
             colorize_bool!(self.isolation),
             "RFG:".bold(),
             colorize_bool!(self.rfg),
+            "RWX Sections:".bold(),
+            if self.rwx {
+                self.rwx_sections.len().to_string().red()
+            } else {
+                self.rwx_sections.len().to_string().green()
+            },
             "SafeSEH:".bold(),
-            colorize_bool!(self.safeseh),
+            self.safeseh,
             "SEH:".bold(),
-            colorize_bool!(self.seh)
-        )
+            colorize_bool!(self.seh),
+            "Subsystem:".bold(),
+            self.subsystem
+        )?;
+        if self.r2r {
+            write!(f, " {}", NATIVE_NOTE.dimmed())?;
+        } else if self.clr {
+            write!(f, " {}", MANAGED_NOTE.dimmed())?;
+        }
+        if !self.warnings.is_empty() {
+            write!(
+                f,
+                " {} {}",
+                "Warnings:".bold(),
+                self.warnings.join("; ").red()
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -409,20 +1052,46 @@ impl fmt::Display for CheckSecResults {
 pub trait Properties {
     /// check for both `IMAGE_DLLCHARACTERISTICS_DYNAMIC_BASE` *(0x0040)* and
     /// `IMAGE_DLLCHARACTERISTICS_HIGH_ENTROPY_VA` *(0x0020)* in
-    /// `DllCharacteristics` within the `IMAGE_OPTIONAL_HEADER32/64`
+    /// `DllCharacteristics` within the `IMAGE_OPTIONAL_HEADER32/64`,
+    /// cross-checked with [`Self::has_relocations`] as the loader can not
+    /// rebase an image without relocations
     fn has_aslr(&self) -> ASLR;
-    /// check flags in the `IMAGE_LOAD_CONFIG_CODE_INTEGRITY` structure linked
-    /// from `IMAGE_LOAD_CONFIG_DIRECTORY32/64` within the
+    /// check for a `WIN_CERT_TYPE_PKCS_SIGNED_DATA` *(0x0002)* entry in the
+    /// attribute certificate table, no signature verification is done
+    ///
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// to read & parse required information from the underlying
+    /// binary file
+    fn has_authenticode(&self, mem: &[u8]) -> bool;
+    /// read the `WIN_CERTIFICATE` header at the file offset stored in the
+    /// `IMAGE_DIRECTORY_ENTRY_SECURITY` data directory of the
     /// `IMAGE_OPTIONAL_HEADER32/64`
     ///
-    /// requires a
-    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html)
-    /// of the original file to read & parse required information from the
-    /// underlying binary file
-    fn has_authenticode(&self, mem: &memmap::Mmap) -> bool;
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// to read & parse required information from the underlying
+    /// binary file
+    fn has_certificate(&self, mem: &[u8]) -> Option<Certificate>;
     /// check for `IMAGE_DLLCHARACTERISTICS_GUARD_CF` *(0x4000)* in
     /// `DllCharacteristics` within the `IMAGE_OPTIONAL_HEADER32/64`
     fn has_cfg(&self) -> bool;
+    /// check `guard_cf_function_table` and `guard_cf_function_count` from
+    /// the `IMAGE_LOAD_CONFIG_DIRECTORY32/64` for a populated table of valid
+    /// indirect call targets
+    ///
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// to read & parse required information from the underlying
+    /// binary file
+    fn has_cfg_function_table(&self, mem: &[u8]) -> bool;
+    /// compare the stored `CheckSum` of the `IMAGE_OPTIONAL_HEADER32/64`
+    /// with [`Self::get_checksum`]
+    ///
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// to compute the checksum over
+    fn has_checksum(&self, mem: &[u8]) -> Checksum;
     /// check for Common Language Runtime header within the
     /// `IMAGE_OPTIONAL_HEADER32/64`
     fn has_clr(&self) -> bool;
@@ -439,41 +1108,115 @@ pub trait Properties {
     /// `IMAGE_LOAD_CONFIG_DIRECTORY32/64` from the
     /// `IMAGE_OPTIONAL_HEADER32/64`
     ///
-    /// requires a
-    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html)
-    /// of the original file to read & parse required information from the
-    /// underlying binary file
-    fn has_gs(&self, mem: &memmap::Mmap) -> bool;
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// to read & parse required information from the underlying
+    /// binary file
+    fn has_gs(&self, mem: &[u8]) -> bool;
     /// check for `IMAGE_DLLCHARACTERISTICS_HIGH_ENTROPY_VA` *(`0x0020`)* in
     /// `DllCharacteristics` within the `IMAGE_OPTIONAL_HEADER32/64`
     fn has_high_entropy_va(&self) -> bool;
     /// check for `IMAGE_DLLCHARACTERISTICS_NO_ISOLATION` *(0x0200)* in
     /// `DllCharacteristics` within the `IMAGE_OPTIONAL_HEADER32/64`
     fn has_isolation(&self) -> bool;
+    /// check for a readable `IMAGE_LOAD_CONFIG_DIRECTORY32/64` from the
+    /// `IMAGE_OPTIONAL_HEADER32/64`
+    fn has_load_config(&self, mem: &[u8]) -> bool;
+    /// check the `ManagedNativeHeader` of the Common Language Runtime
+    /// header for a `READYTORUN_HEADER` *(`RTR` signature)*, or the
+    /// `COMIMAGE_FLAGS_IL_LIBRARY` *(0x4)* flag of NGEN native images
+    ///
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// to read & parse required information from the underlying
+    /// binary file
+    fn has_r2r(&self, mem: &[u8]) -> bool;
+    /// check for a non-empty `IMAGE_DIRECTORY_ENTRY_BASERELOC` data
+    /// directory and the absence of `IMAGE_FILE_RELOCS_STRIPPED` *(0x0001)*
+    /// in the `Characteristics` of the COFF file header
+    fn has_relocations(&self) -> bool;
     /// check `guard_flags` for `IMAGE_GUARD_RF_INSTRUMENTED` *(0x00020000)*
     /// along with `IMAGE_GUARD_RF_ENABLE` *(`0x00040000`)* or
     /// `IMAGE_GUARD_RF_STRICT` *(`0x0008_0000`)* in `IMAGE_DATA_DIRECTORY`
     /// from the `IMAGE_OPTIONAL_HEADER32/64`
     ///
-    /// requires a
-    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html)
-    /// of the original file to read & parse required information from the
-    /// underlying binary file
-    fn has_rfg(&self, mem: &memmap::Mmap) -> bool;
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// to read & parse required information from the underlying
+    /// binary file
+    fn has_rfg(&self, mem: &[u8]) -> bool;
+    /// check `Characteristics` of the section headers for both
+    /// `IMAGE_SCN_MEM_WRITE` and `IMAGE_SCN_MEM_EXECUTE` and return the
+    /// names of the offending sections
+    fn has_rwx_sections(&self) -> Vec<String>;
     /// check `shandler_count` from `LOAD_CONFIG` in `IMAGE_DATA_DIRECTORY`
     /// linked from the the `IMAGE_OPTIONAL_HEADER32/64`
     ///
-    /// requires a
-    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html)
-    /// of the original file to read and parse required information from the
-    /// underlying binary file
-    fn has_safe_seh(&self, mem: &memmap::Mmap) -> bool;
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// to read and parse required information from the underlying
+    /// binary file
+    fn has_safe_seh(&self, mem: &[u8]) -> bool;
+    /// combine `IMAGE_DLLCHARACTERISTICS_NO_SEH` with [`Self::has_safe_seh`]
+    /// into the SEH state of 32-bit images
+    fn has_safe_seh_mode(&self, mem: &[u8]) -> SEH;
     /// check `IMAGE_DLLCHARACTERISTICS_NO_SEH` from the
     /// `IMAGE_OPTIONAL_HEADER32/64`
     fn has_seh(&self) -> bool;
+    /// count `shandler_count` from `LOAD_CONFIG` in `IMAGE_DATA_DIRECTORY`
+    /// linked from the the `IMAGE_OPTIONAL_HEADER32/64`
+    fn has_seh_handlers(&self, mem: &[u8]) -> u64;
+    /// counts the callbacks registered in the `IMAGE_TLS_DIRECTORY32/64`,
+    /// `0` without an `IMAGE_DIRECTORY_ENTRY_TLS` data directory
+    fn has_tls_callbacks(&self, mem: &[u8]) -> u32;
+    /// map `Machine` of the COFF file header to a readable architecture
+    /// name
+    fn get_arch(&self) -> String;
+    /// compute the image checksum *(`CheckSumMappedFile`)*: the 16-bit
+    /// one's complement sum of the file, skipping the `CheckSum` field,
+    /// plus the file size
+    fn get_checksum(&self, mem: &[u8]) -> u32;
+    /// get the DLL names of the `IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT` data
+    /// directory, empty without one
+    ///
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// as delay-load descriptors are not parsed by goblin
+    fn get_delay_imports(&self, mem: &[u8]) -> Vec<String>;
+    /// get `DllCharacteristics` from the `IMAGE_OPTIONAL_HEADER32/64`
+    fn get_dll_characteristics(&self) -> u16;
+    /// find the section header containing the RVA of the
+    /// `IMAGE_DIRECTORY_ENTRY_IAT` data directory
+    fn get_iat_section(&self) -> Option<IatSection>;
+    /// get the DLL names of the import directory, empty without one
+    fn get_imports(&self) -> Vec<String>;
+    /// get the location of the `IMAGE_DIRECTORY_ENTRY_LOAD_CONFIG` data
+    /// directory of the `IMAGE_OPTIONAL_HEADER32/64`
+    fn get_load_config_directory(&self) -> Option<DataDirectory>;
+    /// decode the Rich header between the DOS stub and the PE header
+    ///
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// as the header is not parsed by goblin
+    fn get_rich_header(&self, mem: &[u8]) -> Option<Vec<RichEntry>>;
+    /// compute the entropy of the raw data of every section, from
+    /// `PointerToRawData` up to `SizeOfRawData` of the section headers
+    ///
+    /// requires the raw bytes of the original file *(e.g. a
+    /// [`memmap::Mmap`](https://docs.rs/memmap/0.7.0/memmap/struct.Mmap.html))*
+    /// to read the section contents
+    fn get_section_entropy(&self, mem: &[u8]) -> Vec<SectionEntropy>;
+    /// get `CheckSum` from the `IMAGE_OPTIONAL_HEADER32/64`
+    fn get_stored_checksum(&self) -> u32;
+    /// map `Subsystem` within the `IMAGE_OPTIONAL_HEADER32/64` to a readable
+    /// name
+    fn get_subsystem(&self) -> String;
 }
 impl Properties for PE<'_> {
     fn has_aslr(&self) -> ASLR {
+        if self.has_dynamic_base() && !self.has_relocations() {
+            return ASLR::NoRelocs;
+        }
         if self.has_dynamic_base() & self.has_high_entropy_va() {
             return ASLR::HighEntropyVa;
         } else if self.has_dynamic_base() {
@@ -481,27 +1224,28 @@ impl Properties for PE<'_> {
         }
         ASLR::None
     }
-    fn has_authenticode(&self, mem: &memmap::Mmap) -> bool {
+    fn has_authenticode(&self, mem: &[u8]) -> bool {
         // requires running platform to be Windows for verification
         // just check for existence right now
-        if let Some(optional_header) = self.header.optional_header {
-            let file_alignment = optional_header.windows_fields.file_alignment;
-            let sections = &self.sections;
-            if let Some(load_config_hdr) =
-                optional_header.data_directories.get_load_config_table()
-            {
-                let load_config_val: ImageLoadConfigDirectory =
-                    get_data(mem, sections, *load_config_hdr, file_alignment)
-                        .unwrap();
-                if let Some(certificate_table) =
-                    optional_header.data_directories.get_certificate_table()
-                {
-                    return load_config_val.code_integrity.flags != 0
-                        || certificate_table.virtual_address != 0;
-                }
-            }
+        self.has_certificate(mem).is_some_and(|certificate| {
+            certificate.certificate_type == WIN_CERT_TYPE_PKCS_SIGNED_DATA
+        })
+    }
+    fn has_certificate(&self, mem: &[u8]) -> Option<Certificate> {
+        let optional_header = self.header.optional_header?;
+        let certificate_table =
+            (*optional_header.data_directories.get_certificate_table())?;
+        if certificate_table.size == 0 {
+            return None;
         }
-        false
+        // unlike other data directories this is a file offset, not an RVA
+        let offset = certificate_table.virtual_address as usize;
+        Some(Certificate {
+            certificate_type: mem.pread_with(offset + 6, LE).ok()?,
+            length: mem.pread_with(offset, LE).ok()?,
+            revision: mem.pread_with(offset + 4, LE).ok()?,
+            table_size: certificate_table.size,
+        })
     }
     fn has_cfg(&self) -> bool {
         if let Some(optional_header) = self.header.optional_header {
@@ -514,6 +1258,19 @@ impl Properties for PE<'_> {
         }
         false
     }
+    fn has_cfg_function_table(&self, mem: &[u8]) -> bool {
+        get_load_config(self, mem).is_some_and(|load_config_val| {
+            load_config_val.guard_cf_function_table != 0
+                && load_config_val.guard_cf_function_count != 0
+        })
+    }
+    fn has_checksum(&self, mem: &[u8]) -> Checksum {
+        match self.get_stored_checksum() {
+            0 => Checksum::Zero,
+            stored if stored == self.get_checksum(mem) => Checksum::Valid,
+            _ => Checksum::Invalid,
+        }
+    }
     fn has_clr(&self) -> bool {
         if let Some(optional_header) = self.header.optional_header {
             if optional_header
@@ -559,20 +1316,10 @@ impl Properties for PE<'_> {
         }
         false
     }
-    fn has_gs(&self, mem: &memmap::Mmap) -> bool {
-        if let Some(optional_header) = self.header.optional_header {
-            let file_alignment = optional_header.windows_fields.file_alignment;
-            let sections = &self.sections;
-            if let Some(load_config_hdr) =
-                optional_header.data_directories.get_load_config_table()
-            {
-                let load_config_val: ImageLoadConfigDirectory =
-                    get_data(mem, sections, *load_config_hdr, file_alignment)
-                        .unwrap();
-                return load_config_val.security_cookie != 0;
-            }
-        }
-        false
+    fn has_gs(&self, mem: &[u8]) -> bool {
+        get_load_config(self, mem).is_some_and(|load_config_val| {
+            load_config_val.security_cookie != 0
+        })
     }
     fn has_high_entropy_va(&self) -> bool {
         if let Some(optional_header) = self.header.optional_header {
@@ -596,41 +1343,59 @@ impl Properties for PE<'_> {
         }
         false
     }
-    fn has_rfg(&self, mem: &memmap::Mmap) -> bool {
-        if let Some(optional_header) = self.header.optional_header {
-            let file_alignment = optional_header.windows_fields.file_alignment;
-            let sections = &self.sections;
-            if let Some(load_config_hdr) =
-                optional_header.data_directories.get_load_config_table()
+    fn has_load_config(&self, mem: &[u8]) -> bool {
+        get_load_config(self, mem).is_some()
+    }
+    fn has_r2r(&self, mem: &[u8]) -> bool {
+        has_native_code(self, mem).unwrap_or(false)
+    }
+    fn has_relocations(&self) -> bool {
+        let stripped = self.header.coff_header.characteristics
+            & IMAGE_FILE_RELOCS_STRIPPED
+            != 0;
+        !stripped
+            && self.header.optional_header.is_some_and(|optional_header| {
+                optional_header
+                    .data_directories
+                    .get_base_relocation_table()
+                    .is_some_and(|directory| directory.size > 0)
+            })
+    }
+    fn has_rfg(&self, mem: &[u8]) -> bool {
+        if let Some(load_config_val) = get_load_config(self, mem) {
+            let guard_flags = load_config_val.guard_flags;
+            if (guard_flags & IMAGE_GUARD_RF_INSTRUMENTED) != 0
+                && (guard_flags & IMAGE_GUARD_RF_ENABLE) != 0
+                || (guard_flags & IMAGE_GUARD_RF_STRICT) != 0
             {
-                let load_config_val: ImageLoadConfigDirectory =
-                    get_data(mem, sections, *load_config_hdr, file_alignment)
-                        .unwrap();
-                let guard_flags = load_config_val.guard_flags;
-                if (guard_flags & IMAGE_GUARD_RF_INSTRUMENTED) != 0
-                    && (guard_flags & IMAGE_GUARD_RF_ENABLE) != 0
-                    || (guard_flags & IMAGE_GUARD_RF_STRICT) != 0
-                {
-                    return true;
-                }
+                return true;
             }
         }
         false
     }
-    fn has_safe_seh(&self, mem: &memmap::Mmap) -> bool {
-        if let Some(optional_header) = self.header.optional_header {
-            let file_alignment = optional_header.windows_fields.file_alignment;
-            let sections = &self.sections;
-            if let Some(load_config_hdr) =
-                optional_header.data_directories.get_load_config_table()
-            {
-                let load_config_val: ImageLoadConfigDirectory =
-                    get_data(mem, sections, *load_config_hdr, file_alignment)
-                        .unwrap();
-                return load_config_val.sehandler_count != 0;
-            }
+    fn has_rwx_sections(&self) -> Vec<String> {
+        const RWX: u32 = IMAGE_SCN_MEM_WRITE | IMAGE_SCN_MEM_EXECUTE;
+        self.sections
+            .iter()
+            .filter(|section| section.characteristics & RWX == RWX)
+            .map(|section| section.name().unwrap_or_default().to_string())
+            .collect()
+    }
+    fn has_safe_seh(&self, mem: &[u8]) -> bool {
+        get_load_config(self, mem).is_some_and(|load_config_val| {
+            load_config_val.sehandler_count != 0
+        })
+    }
+    fn has_safe_seh_mode(&self, mem: &[u8]) -> SEH {
+        if self.is_64 {
+            SEH::NotApplicable
+        } else if !self.has_seh() {
+            SEH::NoSEH
+        } else if self.has_safe_seh(mem) {
+            SEH::SafeSEH
+        } else {
+            SEH::Unsafe
         }
-        false
     }
     fn has_seh(&self) -> bool {
         #[allow(clippy::match_wildcard_for_single_variants)]
@@ -646,4 +1411,166 @@ impl Properties for PE<'_> {
             _ => false,
         }
     }
+    fn has_seh_handlers(&self, mem: &[u8]) -> u64 {
+        get_load_config(self, mem)
+            .map_or(0, |load_config_val| load_config_val.sehandler_count)
+    }
+    fn has_tls_callbacks(&self, mem: &[u8]) -> u32 {
+        get_tls_callbacks(self, mem).unwrap_or(0)
+    }
+    fn get_arch(&self) -> String {
+        match self.header.coff_header.machine {
+            COFF_MACHINE_X86 => "i386",
+            COFF_MACHINE_X86_64 => "x86_64",
+            COFF_MACHINE_ARM | COFF_MACHINE_ARMNT | COFF_MACHINE_THUMB => {
+                "arm"
+            }
+            COFF_MACHINE_ARM64 => "arm64",
+            COFF_MACHINE_IA64 => "ia64",
+            COFF_MACHINE_RISCV32 => "riscv32",
+            COFF_MACHINE_RISCV64 => "riscv64",
+            machine => return format!("unknown ({:#06x})", machine),
+        }
+        .to_string()
+    }
+    fn get_checksum(&self, mem: &[u8]) -> u32 {
+        // `Signature` and `IMAGE_FILE_HEADER` precede the optional header
+        image_checksum(
+            mem,
+            self.header.dos_header.pe_pointer as usize
+                + 24
+                + OPTIONAL_HEADER_CHECKSUM_OFFSET,
+        )
+    }
+    fn get_delay_imports(&self, mem: &[u8]) -> Vec<String> {
+        get_delay_imports(self, mem).unwrap_or_default()
+    }
+    fn get_iat_section(&self) -> Option<IatSection> {
+        let optional_header = self.header.optional_header?;
+        let directory =
+            (*optional_header.data_directories.get_import_address_table())?;
+        if directory.virtual_address == 0 || directory.size == 0 {
+            return None;
+        }
+        self.sections
+            .iter()
+            .find(|section| {
+                let size = section.virtual_size.max(section.size_of_raw_data);
+                section.virtual_address <= directory.virtual_address
+                    && directory.virtual_address
+                        < section.virtual_address.saturating_add(size)
+            })
+            .map(|section| IatSection {
+                characteristics: section.characteristics,
+                name: section.name().unwrap_or_default().to_string(),
+            })
+    }
+    fn get_imports(&self) -> Vec<String> {
+        self.libraries.iter().map(|name| (*name).to_string()).collect()
+    }
+    fn get_dll_characteristics(&self) -> u16 {
+        self.header.optional_header.map_or(0, |optional_header| {
+            optional_header.windows_fields.dll_characteristics
+        })
+    }
+    fn get_load_config_directory(&self) -> Option<DataDirectory> {
+        let optional_header = self.header.optional_header?;
+        let directory =
+            (*optional_header.data_directories.get_load_config_table())?;
+        Some(DataDirectory {
+            rva: directory.virtual_address,
+            size: directory.size,
+        })
+    }
+    fn get_rich_header(&self, mem: &[u8]) -> Option<Vec<RichEntry>> {
+        let read = |offset: usize| mem.pread_with::<u32>(offset, LE).ok();
+        // the header is dword aligned and ends before the PE header, `Rich`
+        // and its key may take up its last 8 bytes
+        let end = (self.header.dos_header.pe_pointer as usize).min(mem.len());
+        let rich = (0x40..end.saturating_sub(7))
+            .step_by(4)
+            .find(|offset| read(*offset) == Some(RICH_MARKER))?;
+        let key = read(rich + 4)?;
+        let dans = (0x40..rich).step_by(4).rev().find(|offset| {
+            read(*offset).map(|value| value ^ key) == Some(DANS_MARKER)
+        })?;
+        // `DanS` is followed by three zeroed padding dwords
+        let entries = (dans + 16..rich)
+            .step_by(8)
+            .filter_map(|offset| {
+                let comp_id = read(offset)? ^ key;
+                let count = read(offset + 4)? ^ key;
+                #[allow(clippy::cast_possible_truncation)]
+                Some(RichEntry {
+                    product_id: (comp_id >> 16) as u16,
+                    build: comp_id as u16,
+                    count,
+                })
+            })
+            .collect();
+        Some(entries)
+    }
+    fn get_section_entropy(&self, mem: &[u8]) -> Vec<SectionEntropy> {
+        self.sections
+            .iter()
+            .filter_map(|section| {
+                let start = section.pointer_to_raw_data as usize;
+                let end =
+                    start.checked_add(section.size_of_raw_data as usize)?;
+                let data = mem.get(start..end)?;
+                if data.is_empty() {
+                    return None;
+                }
+                let name = section.name().unwrap_or_default().to_string();
+                Some(SectionEntropy::new(name, data))
+            })
+            .collect()
+    }
+    fn get_stored_checksum(&self) -> u32 {
+        self.header.optional_header.map_or(0, |optional_header| {
+            optional_header.windows_fields.check_sum
+        })
+    }
+    fn get_subsystem(&self) -> String {
+        let subsystem = self
+            .header
+            .optional_header
+            .map(|optional_header| optional_header.windows_fields.subsystem);
+        match subsystem {
+            Some(IMAGE_SUBSYSTEM_NATIVE) => "Native",
+            Some(IMAGE_SUBSYSTEM_WINDOWS_GUI) => "Windows GUI",
+            Some(IMAGE_SUBSYSTEM_WINDOWS_CUI) => "Windows CUI",
+            Some(IMAGE_SUBSYSTEM_OS2_CUI) => "OS/2 CUI",
+            Some(IMAGE_SUBSYSTEM_POSIX_CUI) => "POSIX CUI",
+            Some(IMAGE_SUBSYSTEM_NATIVE_WINDOWS) => "Native Windows",
+            Some(IMAGE_SUBSYSTEM_WINDOWS_CE_GUI) => "Windows CE GUI",
+            Some(IMAGE_SUBSYSTEM_EFI_APPLICATION) => "EFI Application",
+            Some(IMAGE_SUBSYSTEM_EFI_BOOT_SERVICE_DRIVER) => {
+                "EFI Boot Service Driver"
+            }
+            Some(IMAGE_SUBSYSTEM_EFI_RUNTIME_DRIVER) => "EFI Runtime Driver",
+            Some(IMAGE_SUBSYSTEM_EFI_ROM) => "EFI ROM",
+            Some(IMAGE_SUBSYSTEM_XBOX) => "Xbox",
+            Some(IMAGE_SUBSYSTEM_WINDOWS_BOOT_APPLICATION) => {
+                "Windows Boot Application"
+            }
+            _ => "Unknown",
+        }
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_checksum_of_odd_length() {
+        // words 0x0201 + 0x0403 + 0xffff fold to 0x0604, the `CheckSum`
+        // dword at offset 4 is skipped and the trailing byte adds 0x0005,
+        // plus the length of 11 bytes gives 0x0614
+        let mem =
+            [0x01, 0x02, 0x03, 0x04, 0xaa, 0xbb, 0xcc, 0xdd, 0xff, 0xff, 0x05];
+        assert_eq!(image_checksum(&mem, 4), 0x0614);
+    }
 }
@@ -0,0 +1,181 @@
+#[cfg(feature = "elf")]
+use checksec::binary::ELF_MITIGATIONS;
+#[cfg(feature = "macho")]
+use checksec::binary::MACHO_MITIGATIONS;
+#[cfg(feature = "pe")]
+use checksec::binary::PE_MITIGATIONS;
+use checksec::binary::{
+    mitigation_names, BinSpecificProperties, Binary, Mitigation,
+};
+use serde::Deserialize;
+
+use std::fs;
+use std::path::Path;
+
+/// parse a comma separated list of mitigation names, returning the first
+/// unknown name on failure
+pub fn parse_required(list: &str) -> Result<Vec<&str>, String> {
+    let known = mitigation_names();
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            if known.contains(&name) {
+                Ok(name)
+            } else {
+                Err(format!(
+                    "unknown mitigation {} [possible values: {}]",
+                    name,
+                    known.join(", ")
+                ))
+            }
+        })
+        .collect()
+}
+
+/// required mitigations that are not fully enabled for `binary`
+///
+/// mitigations that do not apply to the binary format (or are not
+/// applicable to this particular binary) are ignored, partial mitigations
+/// count as missing
+pub fn violations<'a>(binary: &Binary, required: &[&'a str]) -> Vec<&'a str> {
+    let mitigations = binary.properties.mitigations();
+    required
+        .iter()
+        .copied()
+        .filter(|name| {
+            mitigations.iter().any(|(mitigation, state)| {
+                mitigation == name
+                    && !matches!(
+                        state,
+                        Mitigation::Enabled | Mitigation::NotApplicable
+                    )
+            })
+        })
+        .collect()
+}
+
+/// mitigations a binary format must have enabled
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    /// mitigations that must be enabled
+    #[serde(default)]
+    pub require: Vec<String>,
+    /// required mitigations that may also be partially enabled, e.g.
+    /// partial RELRO
+    #[serde(default)]
+    pub allow_partial: Vec<String>,
+}
+
+/// required mitigations per binary format, loaded from a yaml file
+///
+/// ```yaml
+/// elf:
+///   require: [canary, nx, pie, relro]
+///   allow_partial: [relro]
+/// pe:
+///   require: [aslr, dep, cfg]
+/// macho:
+///   require: [canary, pie]
+/// ```
+///
+/// formats without a section are not checked
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default)]
+    pub elf: Option<Rule>,
+    #[serde(default)]
+    pub pe: Option<Rule>,
+    #[serde(default)]
+    pub macho: Option<Rule>,
+}
+
+/// mitigation names a policy section may refer to
+fn section_names(section: &str) -> &'static [&'static str] {
+    match section {
+        #[cfg(feature = "elf")]
+        "elf" => &ELF_MITIGATIONS,
+        #[cfg(feature = "pe")]
+        "pe" => &PE_MITIGATIONS,
+        #[cfg(feature = "macho")]
+        "macho" => &MACHO_MITIGATIONS,
+        _ => &[],
+    }
+}
+
+impl Policy {
+    /// read and validate a policy file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        let policy: Self =
+            serde_yaml::from_str(&data).map_err(|err| err.to_string())?;
+        for (section, rule) in policy.sections() {
+            let known = section_names(section);
+            for name in rule.require.iter().chain(&rule.allow_partial) {
+                if !known.contains(&name.as_str()) {
+                    return Err(format!(
+                        "unknown {} mitigation {} [possible values: {}]",
+                        section,
+                        name,
+                        known.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(policy)
+    }
+    fn sections(&self) -> Vec<(&'static str, &Rule)> {
+        [("elf", &self.elf), ("pe", &self.pe), ("macho", &self.macho)]
+            .iter()
+            .filter_map(|(section, rule)| {
+                rule.as_ref().map(|rule| (*section, rule))
+            })
+            .collect()
+    }
+    /// rule for the format of `binary`
+    fn rule(&self, binary: &Binary) -> Option<&Rule> {
+        match binary.properties {
+            #[cfg(feature = "elf")]
+            BinSpecificProperties::Elf(_) => self.elf.as_ref(),
+            #[cfg(feature = "pe")]
+            BinSpecificProperties::PE(_) => self.pe.as_ref(),
+            #[cfg(feature = "macho")]
+            BinSpecificProperties::MachO(_) => self.macho.as_ref(),
+            // there are no mitigations to require
+            #[cfg(feature = "wasm")]
+            BinSpecificProperties::Wasm(_) => None,
+        }
+    }
+    /// required mitigations `binary` does not meet, partially enabled ones
+    /// are marked as such
+    ///
+    /// like [`violations`], mitigations that are not applicable to the
+    /// binary are ignored
+    pub fn violations(&self, binary: &Binary) -> Vec<String> {
+        let rule = match self.rule(binary) {
+            Some(rule) => rule,
+            None => return Vec::new(),
+        };
+        let mitigations = binary.properties.mitigations();
+        rule.require
+            .iter()
+            .filter_map(|name| {
+                let state = mitigations
+                    .iter()
+                    .find(|(mitigation, _)| mitigation == name)
+                    .map(|(_, state)| *state)?;
+                match state {
+                    Mitigation::Disabled => Some(name.clone()),
+                    Mitigation::Partial
+                        if !rule.allow_partial.contains(name) =>
+                    {
+                        Some(format!("{} (partial)", name))
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,53 @@
+use checksec::binary::{Binary, ParseError};
+use checksec::parse_bytes;
+
+use std::io::{self, Read};
+
+/// largest download read into memory when `--max-file-size` is not given
+const MAX_DOWNLOAD_SIZE: u64 = 1 << 30;
+
+fn download_error(msg: String) -> ParseError {
+    io::Error::other(msg).into()
+}
+
+/// download `url` into memory, up to `max_file_size` bytes, and parse it
+/// with the url as file name
+pub fn fetch(
+    url: &str,
+    max_file_size: Option<u64>,
+) -> Result<Vec<Binary>, ParseError> {
+    let limit = max_file_size.unwrap_or(MAX_DOWNLOAD_SIZE);
+    let response = match ureq::get(url).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(code, response)) => {
+            return Err(download_error(format!(
+                "download failed with HTTP {} {}",
+                code,
+                response.status_text()
+            )))
+        }
+        Err(err) => {
+            return Err(download_error(format!("download failed: {}", err)))
+        }
+    };
+    // redirects are followed, anything else but the file is an error
+    if response.status() != 200 {
+        return Err(download_error(format!(
+            "download failed with HTTP {} {}",
+            response.status(),
+            response.status_text()
+        )));
+    }
+    let mut buffer: Vec<u8> = Vec::new();
+    response
+        .into_reader()
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut buffer)?;
+    if buffer.len() as u64 > limit {
+        return Err(download_error(format!(
+            "download exceeds the maximum size of {} bytes",
+            limit
+        )));
+    }
+    parse_bytes(url, &buffer)
+}
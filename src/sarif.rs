@@ -0,0 +1,127 @@
+use checksec::binary::{mitigation_names, Binary, Mitigation};
+use serde_json::{json, Value};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// stable rule id for a mitigation name, e.g. `MISSING_NX`
+fn rule_id(name: &str) -> String {
+    format!("MISSING_{}", name.to_uppercase())
+}
+
+/// one rule per known mitigation, independent of the scanned binaries so
+/// `ruleIndex` values stay stable between runs
+pub fn rules() -> Vec<Value> {
+    mitigation_names()
+        .iter()
+        .map(|name| {
+            json!({
+                "id": rule_id(name),
+                "name": name,
+                "shortDescription": {
+                    "text": format!("{} is not enabled", name)
+                },
+                "defaultConfiguration": { "level": "error" }
+            })
+        })
+        .collect()
+}
+
+/// sarif results for every disabled (`error`) or partially enabled
+/// (`warning`) mitigation of `binary`
+pub fn results(binary: &Binary) -> Vec<Value> {
+    let names = mitigation_names();
+    let target = match &binary.arch {
+        Some(arch) => {
+            format!("{} ({}, {})", binary.file, binary.binarytype, arch)
+        }
+        None => format!("{} ({})", binary.file, binary.binarytype),
+    };
+    binary
+        .properties
+        .mitigations()
+        .into_iter()
+        .filter_map(|(name, state)| {
+            let level = match state {
+                Mitigation::Disabled => "error",
+                Mitigation::Partial => "warning",
+                _ => return None,
+            };
+            Some(json!({
+                "ruleId": rule_id(name),
+                "ruleIndex": names.iter().position(|known| *known == name),
+                "level": level,
+                "message": {
+                    "text": format!("{} is missing {}", target, name)
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": binary.file }
+                    }
+                }]
+            }))
+        })
+        .collect()
+}
+
+/// complete sarif 2.1.0 log with a single run
+pub fn log<'a>(bins: impl IntoIterator<Item = &'a Binary>) -> Value {
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "checksec",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/etke/checksec.rs",
+                    "rules": rules()
+                }
+            },
+            "results": bins.into_iter().flat_map(results).collect::<Vec<Value>>()
+        }]
+    })
+}
+
+#[cfg(all(test, feature = "elf"))]
+mod tests {
+    use super::*;
+    use checksec::parse_path;
+    use std::path::Path;
+
+    #[test]
+    fn results_of_known_binaries() {
+        for file in [
+            "test/binaries/true-x86_64",
+            "test/binaries/true-x86_64.rpath.modified",
+            "test/binaries/true-x86_64.runpath.modified",
+        ] {
+            let binaries = parse_path(Path::new(file)).unwrap();
+            let results = results(&binaries[0]);
+            let found: Vec<(&str, &str)> = results
+                .iter()
+                .map(|result| {
+                    (
+                        result["ruleId"].as_str().unwrap(),
+                        result["level"].as_str().unwrap(),
+                    )
+                })
+                .collect();
+            assert_eq!(
+                found,
+                [
+                    ("MISSING_CLANG_CFI", "error"),
+                    ("MISSING_CLANG_SAFESTACK", "error"),
+                    ("MISSING_IBT", "error"),
+                    ("MISSING_PIE", "error"),
+                    ("MISSING_RELRO", "warning"),
+                    ("MISSING_SHSTK", "error"),
+                ]
+            );
+            for result in &results {
+                let uri = &result["locations"][0]["physicalLocation"]
+                    ["artifactLocation"]["uri"];
+                assert_eq!(uri, file);
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Lazily scans directory trees for binaries
+//!
+//! [`scan_dir`] yields the results of one file after another while the
+//! directory tree is walked, so large trees can be processed without
+//! holding every result in memory. It is the in-process counterpart of the
+//! `--ndjson` output of the command line utility.
+//!
+//! The walk itself is sequential. To check files in parallel, hand the
+//! paths from [`files`] to [`scan_file`] on a thread pool such as rayon:
+//!
+//! ```rust,no_run
+//! use checksec::scan::{files, scan_file, ScanOptions};
+//! use rayon::prelude::*;
+//! use std::path::Path;
+//!
+//! let options = ScanOptions::default();
+//! files(Path::new("/usr/bin"), &options)
+//!     .par_bridge()
+//!     .flat_map_iter(|file| match file {
+//!         Ok(file) => scan_file(&file, &options),
+//!         Err(err) => vec![Err(err)],
+//!     })
+//!     .for_each(|result| match result {
+//!         Ok(binary) => println!("{}", binary),
+//!         Err(err) => eprintln!("{}", err),
+//!     });
+//! ```
+use goblin::error::Error;
+use ignore::WalkBuilder;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::{fmt, io};
+
+use crate::binary::{Binary, ParseError};
+use crate::parse_path_with_limit;
+
+/// How directory trees are walked and files are read
+#[derive(Clone, Debug, Default)]
+pub struct ScanOptions {
+    /// follow symbolic links, yielding files reachable through several
+    /// links only once
+    pub follow_symlinks: bool,
+    /// descend at most this many directories, `1` only scans the contents
+    /// of the given directory
+    pub max_depth: Option<usize>,
+    /// largest file read into memory when it can not be memory mapped,
+    /// see [`parse_path_with_limit`]
+    pub max_file_size: Option<u64>,
+    /// also scan hidden files and files matched by `.gitignore`, `.ignore`
+    /// and similar filters
+    pub no_ignore: bool,
+}
+
+/// Error of a single file or directory entry, the scan continues with the
+/// next file
+#[derive(Debug)]
+pub struct ScanError {
+    /// file the error occurred for, `None` for errors of the walk itself
+    pub path: Option<PathBuf>,
+    pub error: ParseError,
+}
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}: {}", path.display(), self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+impl std::error::Error for ScanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// files below `path` in walk order, without checking them
+pub fn files(
+    path: &Path,
+    options: &ScanOptions,
+) -> impl Iterator<Item = Result<PathBuf, ScanError>> {
+    let mut builder = WalkBuilder::new(path);
+    // loops are detected and reported as walk errors by `ignore`
+    builder.follow_links(options.follow_symlinks);
+    builder.standard_filters(!options.no_ignore);
+    builder.max_depth(options.max_depth);
+    let follow_symlinks = options.follow_symlinks;
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    builder.build().filter_map(move |entry| match entry {
+        Ok(entry) if entry.file_type().is_some_and(|ft| ft.is_file()) => {
+            if follow_symlinks {
+                let real = entry
+                    .path()
+                    .canonicalize()
+                    .unwrap_or_else(|_| entry.path().to_path_buf());
+                if !seen.insert(real) {
+                    return None;
+                }
+            }
+            Some(Ok(entry.into_path()))
+        }
+        Ok(_) => None,
+        Err(err) => Some(Err(ScanError {
+            path: None,
+            error: io::Error::other(err.to_string()).into(),
+        })),
+    })
+}
+
+/// check a single `file`, every architecture of a fat `MachO` binary is
+/// its own result
+///
+/// files of unsupported formats and empty files are expected in directory
+/// trees and yield no results
+pub fn scan_file(
+    file: &Path,
+    options: &ScanOptions,
+) -> Vec<Result<Binary, ScanError>> {
+    let error = |error| ScanError { path: Some(file.to_path_buf()), error };
+    match parse_path_with_limit(file, options.max_file_size) {
+        Ok(binaries) => binaries.into_iter().map(Ok).collect(),
+        Err(ParseError::FatArch { binaries, errors }) => binaries
+            .into_iter()
+            .map(Ok)
+            .chain([Err(error(ParseError::FatArch {
+                binaries: Vec::new(),
+                errors,
+            }))])
+            .collect(),
+        Err(
+            ParseError::Empty
+            | ParseError::TooSmall(_)
+            | ParseError::Goblin(Error::BadMagic(_)),
+        ) => Vec::new(),
+        Err(err) => vec![Err(error(err))],
+    }
+}
+
+/// check every file below `path`, yielding the results lazily as the walk
+/// proceeds
+///
+/// **Example**
+///
+/// ```rust,no_run
+/// use checksec::{scan_dir, ScanOptions};
+/// use std::path::Path;
+///
+/// for result in scan_dir(Path::new("/usr/bin"), &ScanOptions::default()) {
+///     match result {
+///         Ok(binary) => println!("{}", binary),
+///         Err(err) => eprintln!("{}", err),
+///     }
+/// }
+/// ```
+pub fn scan_dir(
+    path: &Path,
+    options: &ScanOptions,
+) -> impl Iterator<Item = Result<Binary, ScanError>> {
+    let options = options.clone();
+    files(path, &options).flat_map(move |file| match file {
+        Ok(file) => scan_file(&file, &options),
+        Err(err) => vec![Err(err)],
+    })
+}
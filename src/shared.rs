@@ -1,18 +1,57 @@
 //! Implements shared functionalities between elf/macho modules
 #[cfg(feature = "color")]
 use colored::Colorize;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// path prefixes that are commonly world-writable
+const WRITABLE_PREFIXES: [&str; 4] =
+    ["/tmp", "/var/tmp", "/dev/shm", "/run/shm"];
+
+/// prefixes the ELF dynamic loader expands to the object's own directory
+const ELF_ANCHORS: [&str; 2] = ["$ORIGIN", "${ORIGIN}"];
+/// prefixes dyld expands to the executable's or loading image's directory
+const MACHO_ANCHORS: [&str; 2] = ["@executable_path", "@loader_path"];
+
 /// Split contents of `DT_RPATH`/`DT_RUNPATH` or @rpath entries
-#[derive(Debug, Deserialize, Serialize)]
+///
+/// `YesRW` marks entries that allow library hijacking: relative paths
+/// (other than those anchored to `$ORIGIN`, or `@executable_path` and
+/// `@loader_path` for `MachO`), empty entries which resolve to the current
+/// working directory, and world-writable-looking prefixes.
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 pub enum Rpath {
     None,
     Yes(String),
     YesRW(String),
 }
+impl Rpath {
+    /// classify a single `DT_RPATH`/`DT_RUNPATH` search path entry
+    #[must_use]
+    pub fn new(path: &str) -> Self {
+        Self::classify(path, &ELF_ANCHORS)
+    }
+    /// classify a single `LC_RPATH` entry
+    #[must_use]
+    pub fn from_macho(path: &str) -> Self {
+        Self::classify(path, &MACHO_ANCHORS)
+    }
+    fn classify(path: &str, anchors: &[&str]) -> Self {
+        let relative = !path.starts_with('/')
+            && !anchors.iter().any(|anchor| path.starts_with(anchor));
+        let writable = WRITABLE_PREFIXES.iter().any(|prefix| {
+            path == *prefix || path.starts_with(&format!("{}/", prefix))
+        });
+        if relative || writable {
+            Self::YesRW(path.to_string())
+        } else {
+            Self::Yes(path.to_string())
+        }
+    }
+}
 /// wrapper for Vec<Rpath> to allow easy color output per path entry
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 pub struct VecRpath {
     paths: Vec<Rpath>,
 }
@@ -21,6 +60,16 @@ impl VecRpath {
     pub fn new(v: Vec<Rpath>) -> Self {
         Self { paths: v }
     }
+    /// split a `:` separated search path into classified entries
+    #[must_use]
+    pub fn from_search_path(search_path: &str) -> Self {
+        Self::new(search_path.split(':').map(Rpath::new).collect())
+    }
+    /// check for any entry allowing library hijacking
+    #[must_use]
+    pub fn has_writable(&self) -> bool {
+        self.paths.iter().any(|path| matches!(path, Rpath::YesRW(_)))
+    }
 }
 #[cfg(not(feature = "color"))]
 impl fmt::Display for VecRpath {
@@ -43,7 +92,8 @@ impl fmt::Display for VecRpath {
         let mut s: Vec<String> = Vec::<String>::new();
         for v in &self.paths {
             match v {
-                Rpath::Yes(p) | Rpath::YesRW(p) => s.push(p.red().to_string()),
+                Rpath::Yes(p) => s.push(p.red().to_string()),
+                Rpath::YesRW(p) => s.push(p.bright_red().bold().to_string()),
                 Rpath::None => s.push("None".green().to_string()),
             }
         }
@@ -0,0 +1,59 @@
+use checksec::binary::{Binary, Mitigation};
+
+use crate::group::format_name;
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// enabled/partial/applicable counts of a single mitigation
+#[derive(Default)]
+pub struct Count {
+    pub enabled: usize,
+    pub partial: usize,
+    pub total: usize,
+}
+
+/// number of binaries and mitigation counts keyed by format name
+pub type Counts =
+    BTreeMap<&'static str, (usize, BTreeMap<&'static str, Count>)>;
+
+/// count every mitigation per format, binaries where a mitigation is not
+/// applicable are left out of its total
+pub fn count(bins: &[Binary]) -> Counts {
+    let mut formats = Counts::new();
+    for bin in bins {
+        let (binaries, counts) = formats.entry(format_name(bin)).or_default();
+        *binaries += 1;
+        for (name, state) in bin.properties.mitigations() {
+            let count = counts.entry(name).or_default();
+            match state {
+                Mitigation::Enabled => count.enabled += 1,
+                Mitigation::Partial => count.partial += 1,
+                Mitigation::Disabled => (),
+                Mitigation::NotApplicable => continue,
+            }
+            count.total += 1;
+        }
+    }
+    formats
+}
+
+/// per format `enabled/total` counts for every mitigation
+pub fn summarize(bins: &[Binary]) -> String {
+    let mut summary = String::new();
+    for (format, (binaries, counts)) in &count(bins) {
+        writeln!(summary, "{} ({} binaries)", format, binaries).ok();
+        for (name, count) in counts {
+            if count.total == 0 {
+                continue;
+            }
+            write!(summary, "  {}: {}/{}", name, count.enabled, count.total)
+                .ok();
+            if count.partial > 0 {
+                write!(summary, " ({} partial)", count.partial).ok();
+            }
+            summary.push('\n');
+        }
+    }
+    summary
+}
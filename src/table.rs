@@ -0,0 +1,177 @@
+#[cfg(feature = "color")]
+use colored::Colorize;
+
+use checksec::binary::{mitigation_names, Binary, Mitigation};
+
+use crate::fields;
+use crate::output::outln;
+
+use std::env;
+
+/// width used when the terminal width is unknown
+const DEFAULT_WIDTH: usize = 120;
+/// narrowest the file column gets before the table overflows instead
+const MIN_FILE_WIDTH: usize = 16;
+/// padding between columns
+const SEPARATOR: &str = "  ";
+
+/// terminal width as exported by the shell, falling back to
+/// [`DEFAULT_WIDTH`]
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|columns| *columns > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// shorten `file` to `width` characters, keeping the file name end
+fn truncate(file: &str, width: usize) -> String {
+    let chars: Vec<char> = file.chars().collect();
+    if chars.len() <= width {
+        return file.to_string();
+    }
+    let keep = width.saturating_sub(1);
+    let mut short = String::from("\u{2026}");
+    short.extend(&chars[chars.len() - keep..]);
+    short
+}
+
+fn cell(state: Mitigation, width: usize) -> String {
+    let text = match state {
+        Mitigation::Enabled => "yes",
+        Mitigation::Partial => "partial",
+        Mitigation::Disabled => "no",
+        Mitigation::NotApplicable => "-",
+    };
+    let padded = format!("{:width$}", text, width = width);
+    #[cfg(feature = "color")]
+    let padded = match state {
+        Mitigation::Enabled => padded.green().to_string(),
+        Mitigation::Partial => padded.yellow().to_string(),
+        Mitigation::Disabled => padded.red().to_string(),
+        Mitigation::NotApplicable => padded,
+    };
+    padded
+}
+
+/// print one aligned row per binary with a column for every mitigation
+/// that applies to at least one of them
+pub fn print<'a>(bins: impl IntoIterator<Item = &'a Binary>) {
+    let bins: Vec<&Binary> = bins.into_iter().collect();
+    let mitigations: Vec<Vec<(&str, Mitigation)>> =
+        bins.iter().map(|bin| bin.properties.mitigations()).collect();
+    let columns: Vec<&str> = mitigation_names()
+        .into_iter()
+        .filter(|name| {
+            mitigations.iter().flatten().any(|(mitigation, state)| {
+                mitigation == name
+                    && !matches!(state, Mitigation::NotApplicable)
+            })
+        })
+        .collect();
+    let types: Vec<String> = bins
+        .iter()
+        .map(|bin| match &bin.arch {
+            Some(arch) => format!("{:?} {}", bin.binarytype, arch),
+            None => format!("{:?}", bin.binarytype),
+        })
+        .collect();
+    let type_width =
+        types.iter().map(String::len).chain([4]).max().unwrap_or(4);
+    let widths: Vec<usize> =
+        columns.iter().map(|name| name.len().max(7)).collect();
+    let fixed: usize = type_width
+        + widths.iter().sum::<usize>()
+        + SEPARATOR.len() * (columns.len() + 1);
+    let longest = bins
+        .iter()
+        .map(|bin| bin.file.chars().count())
+        .chain([4])
+        .max()
+        .unwrap_or(4);
+    let file_width = longest
+        .min(terminal_width().saturating_sub(fixed))
+        .max(MIN_FILE_WIDTH.min(longest));
+
+    let mut header = vec![
+        format!("{:width$}", "file", width = file_width),
+        format!("{:width$}", "type", width = type_width),
+    ];
+    header.extend(
+        columns
+            .iter()
+            .zip(&widths)
+            .map(|(name, width)| format!("{:width$}", name, width = width)),
+    );
+    let header = header.join(SEPARATOR);
+    #[cfg(feature = "color")]
+    let header = header.bold();
+    outln!("{}", header.trim_end());
+
+    for ((bin, binarytype), states) in
+        bins.iter().zip(&types).zip(&mitigations)
+    {
+        let mut row = vec![
+            format!(
+                "{:width$}",
+                truncate(&bin.file, file_width),
+                width = file_width
+            ),
+            format!("{:width$}", binarytype, width = type_width),
+        ];
+        row.extend(columns.iter().zip(&widths).map(|(name, width)| {
+            let state = states
+                .iter()
+                .find(|(mitigation, _)| mitigation == name)
+                .map_or(Mitigation::NotApplicable, |(_, state)| *state);
+            cell(state, *width)
+        }));
+        outln!("{}", row.join(SEPARATOR).trim_end());
+    }
+}
+
+/// print one aligned row per binary with a column for every selected
+/// field, `-` marks fields that do not apply to the format of a binary
+pub fn print_fields<'a>(
+    bins: impl IntoIterator<Item = &'a Binary>,
+    selected: &[String],
+) {
+    let rows: Vec<Vec<String>> = bins
+        .into_iter()
+        .map(|bin| {
+            fields::values(bin, selected)
+                .into_iter()
+                .map(|value| value.unwrap_or_else(|| "-".to_string()))
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = selected
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            rows.iter()
+                .map(|row| row[idx].chars().count())
+                .chain([name.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let pad = |cells: &[String]| -> String {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join(SEPARATOR)
+            .trim_end()
+            .to_string()
+    };
+    let header = pad(selected);
+    #[cfg(feature = "color")]
+    let header = header.bold();
+    outln!("{}", header);
+    for row in &rows {
+        outln!("{}", pad(row));
+    }
+}
@@ -0,0 +1,212 @@
+//! Implements checksec for `WebAssembly` modules
+//!
+//! goblin does not parse `WebAssembly`, so only the import and export
+//! sections of the module are decoded here. There are no native
+//! mitigations to check, the imported host functions are the closest
+//! analog to the attack surface of a module.
+#[cfg(feature = "color")]
+use colored::Colorize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[cfg(feature = "color")]
+use crate::{colorize_bool, colorize_bool_inverse};
+
+/// `\0asm` magic followed by the version 1 of the binary format
+pub const WASM_MAGIC: &[u8] = b"\0asm\x01\0\0\0";
+
+const SECTION_IMPORT: u8 = 2;
+const SECTION_EXPORT: u8 = 7;
+
+const EXTERNAL_FUNCTION: u8 = 0;
+const EXTERNAL_TABLE: u8 = 1;
+const EXTERNAL_MEMORY: u8 = 2;
+const EXTERNAL_GLOBAL: u8 = 3;
+const EXTERNAL_TAG: u8 = 4;
+
+/// bounds checked reader over the bytes of a module
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+impl<'a> Reader<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+    const fn is_empty(&self) -> bool {
+        self.offset >= self.bytes.len()
+    }
+    fn byte(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.offset)?;
+        self.offset += 1;
+        Some(byte)
+    }
+    /// unsigned LEB128 encoded integer
+    fn leb(&mut self) -> Option<u32> {
+        let mut value: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            value |= u32::from(byte & 0x7f).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+    fn slice(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice =
+            self.bytes.get(self.offset..self.offset.checked_add(len)?)?;
+        self.offset += len;
+        Some(slice)
+    }
+    /// length prefixed utf-8 name
+    fn name(&mut self) -> Option<String> {
+        let len = self.leb()? as usize;
+        self.slice(len).map(|name| String::from_utf8_lossy(name).into_owned())
+    }
+    /// `limits` of a table or memory type
+    fn limits(&mut self) -> Option<()> {
+        let flags = self.byte()?;
+        self.leb()?;
+        if flags & 1 != 0 {
+            self.leb()?;
+        }
+        Some(())
+    }
+}
+
+/// Checksec result struct for `WebAssembly` modules
+///
+/// **Example**
+///
+/// ```rust
+/// use checksec::wasm::CheckSecResults;
+/// use std::fs;
+///
+/// pub fn print_results(binary: &String) {
+///     if let Ok(buf) = fs::read(&binary) {
+///         println!("{:#?}", CheckSecResults::parse(&buf));
+///     }
+/// }
+/// ```
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+#[schemars(rename = "WasmCheckSecResults")]
+pub struct CheckSecResults {
+    /// Exported functions, tables, memories and globals
+    pub exports: u32,
+    /// Imports functions from the host environment
+    pub host_functions: bool,
+    /// Module names imports are taken from *(e.g. `env`,
+    /// `wasi_snapshot_preview1`)*, in first use order
+    pub import_modules: Vec<String>,
+    /// Imported functions, tables, memories and globals
+    pub imports: u32,
+    /// The sections of the module are well formed, the code itself is not
+    /// validated
+    pub valid: bool,
+}
+impl CheckSecResults {
+    /// `bytes` holds the whole module, starting with [`WASM_MAGIC`]
+    #[must_use]
+    pub fn parse(bytes: &[u8]) -> Self {
+        let mut results = Self {
+            exports: 0,
+            host_functions: false,
+            import_modules: Vec::new(),
+            imports: 0,
+            valid: false,
+        };
+        results.valid = bytes.starts_with(WASM_MAGIC)
+            && results.parse_sections(&bytes[WASM_MAGIC.len()..]).is_some();
+        results
+    }
+    fn parse_sections(&mut self, bytes: &[u8]) -> Option<()> {
+        let mut reader = Reader::new(bytes);
+        while !reader.is_empty() {
+            let id = reader.byte()?;
+            let size = reader.leb()? as usize;
+            let mut section = Reader::new(reader.slice(size)?);
+            match id {
+                SECTION_IMPORT => self.parse_imports(&mut section)?,
+                SECTION_EXPORT => self.parse_exports(&mut section)?,
+                _ => (),
+            }
+        }
+        Some(())
+    }
+    fn parse_imports(&mut self, section: &mut Reader) -> Option<()> {
+        for _ in 0..section.leb()? {
+            let module = section.name()?;
+            section.name()?;
+            match section.byte()? {
+                EXTERNAL_FUNCTION => {
+                    section.leb()?;
+                    self.host_functions = true;
+                }
+                EXTERNAL_TABLE => {
+                    section.byte()?;
+                    section.limits()?;
+                }
+                EXTERNAL_MEMORY => section.limits()?,
+                EXTERNAL_GLOBAL => {
+                    section.slice(2)?;
+                }
+                EXTERNAL_TAG => {
+                    section.byte()?;
+                    section.leb()?;
+                }
+                _ => return None,
+            }
+            self.imports += 1;
+            if !self.import_modules.contains(&module) {
+                self.import_modules.push(module);
+            }
+        }
+        Some(())
+    }
+    fn parse_exports(&mut self, section: &mut Reader) -> Option<()> {
+        for _ in 0..section.leb()? {
+            section.name()?;
+            section.byte()?;
+            section.leb()?;
+            self.exports += 1;
+        }
+        Some(())
+    }
+    /// supporting detail for verbose output as `(label, evidence)` pairs
+    #[must_use]
+    pub fn evidence(&self) -> Vec<(&'static str, String)> {
+        if self.import_modules.is_empty() {
+            return Vec::new();
+        }
+        vec![("Import Modules", self.import_modules.join(", "))]
+    }
+}
+
+impl fmt::Display for CheckSecResults {
+    #[cfg(not(feature = "color"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Exports: {:3} Host Functions: {} Imports: {:3} Valid: {}",
+            self.exports, self.host_functions, self.imports, self.valid
+        )
+    }
+    #[cfg(feature = "color")]
+    /// Colorized human readable format output
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:3} {} {} {} {:3} {} {}",
+            "Exports:".bold(),
+            self.exports,
+            "Host Functions:".bold(),
+            colorize_bool_inverse!(self.host_functions),
+            "Imports:".bold(),
+            self.imports,
+            "Valid:".bold(),
+            colorize_bool!(self.valid)
+        )
+    }
+}